@@ -1,15 +1,242 @@
-use zombiesplit::config;
+use anyhow::anyhow;
+use std::{
+    cell::RefCell,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+use structopt::StructOpt;
+use zombiesplit::{
+    config::{self, game::Severity},
+    db,
+    model::{practice, run::Run, session},
+    presenter::pick,
+    view::hotkeys,
+};
+
+/// A speedrun split timer.
+#[derive(StructOpt)]
+struct Args {
+    #[structopt(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(StructOpt)]
+enum Command {
+    /// Runs the split timer (the default).
+    Run {
+        /// The ID of the game to run, or the path to its config.
+        game: Option<String>,
+        /// The ID of the category to run; needed if the game has more than
+        /// one.
+        category: Option<String>,
+    },
+    /// Lists the games in the library, or the categories of one game.
+    List {
+        /// The ID of the game whose categories should be listed, or the path
+        /// to its config.
+        game: Option<String>,
+    },
+    /// Checks a game config, reporting every problem found.
+    Check {
+        /// The game config to check.
+        #[structopt(parse(from_os_str))]
+        file: PathBuf,
+    },
+}
 
 fn main() {
-    run().unwrap()
+    let args = Args::from_args();
+    let command = args.command.unwrap_or(Command::Run {
+        game: None,
+        category: None,
+    });
+    match command {
+        Command::Run { game, category } => run(game, category).unwrap(),
+        Command::List { game } => list(game).unwrap(),
+        Command::Check { file } => {
+            if !check(&file).unwrap() {
+                std::process::exit(1)
+            }
+        }
+    }
 }
 
-fn run() -> anyhow::Result<()> {
+fn run(game: Option<String>, category: Option<String>) -> anyhow::Result<()> {
     let sys = config::System::load("sys.toml")?;
-    let cfg = config::Game::load("soniccd.toml")?;
-    let run = cfg.to_run("btg")?;
-    let p = zombiesplit::Presenter::new(run);
-    zombiesplit::View::new(sys.ui)?.spawn(p)?.run()?;
+    let mut lib = config::Library::open(&sys.library_path)?;
+    if let Err(e) = lib.watch() {
+        eprintln!("warning: not watching game library for changes: {e}");
+    }
+    // The picker reads history from the same connection the observer writes
+    // it to.
+    let db = Rc::new(RefCell::new(db::Db::open(&sys.db_path)?));
+    let catalogue = Catalogue {
+        lib,
+        db: Rc::clone(&db),
+    };
+
+    let mut p = zombiesplit::Presenter::new(Run::default());
+    p.set_source(Box::new(catalogue));
+    match (game, category) {
+        (None, _) => p.pick_game(),
+        (Some(game), None) => p.pick_category(&game),
+        (Some(game), Some(category)) => p
+            .load(&game, &category)
+            .map_err(|e| anyhow!("couldn't load {} {}: {}", game, category, e))?,
+    }
+    p.add_observer(Box::new(db));
+    p.add_observer(Box::new(SummaryPrinter));
+    let view = zombiesplit::View::new(sys.ui, sys.keys)?;
+    let mut instance = view.spawn(p)?;
+    if let Some(ref cfg) = sys.gamepad {
+        instance.set_gamepads(view.gamepads(cfg)?);
+    }
+    if let Some(ref cfg) = sys.hotkeys {
+        match hotkeys::Listener::spawn(cfg) {
+            Ok(listener) => instance.set_hotkeys(listener),
+            Err(e) => eprintln!("warning: global hotkeys disabled: {e}"),
+        }
+    }
+    instance.run()?;
+    if let Some((w, h)) = instance.resized() {
+        if let Err(e) = config::System::save_window_size("sys.toml", w, h) {
+            eprintln!("warning: couldn't remember window size: {e}");
+        }
+    }
 
     Ok(())
 }
+
+/// Lists the games in the library, or the categories of `game` if given.
+fn list(game: Option<String>) -> anyhow::Result<()> {
+    let sys = config::System::load("sys.toml")?;
+    let lib = config::Library::open(&sys.library_path)?;
+    if let Some(game) = game {
+        let cfg = find_game(&lib, &game)?;
+        for id in cfg.category_ids() {
+            println!("{id}\t{}", cfg.category(&id)?.name);
+        }
+        return Ok(());
+    }
+
+    for cfg in lib.games() {
+        println!("{}\t{}", cfg.id, cfg.name);
+    }
+    for (path, e) in lib.failures() {
+        eprintln!("couldn't load {}: {e}", path.display());
+    }
+    Ok(())
+}
+
+/// Finds the game `game` by ID in `lib`, or failing that, loads it as a path
+/// to a game config.
+fn find_game(lib: &config::Library, game: &str) -> anyhow::Result<config::Game> {
+    if let Some(cfg) = lib.game(game) {
+        return Ok(cfg.clone());
+    }
+    let path = Path::new(game);
+    if !path.is_file() {
+        return Err(anyhow!(
+            "no game '{game}' in library {}",
+            lib.path().display()
+        ));
+    }
+    Ok(config::Game::load(path)?)
+}
+
+/// Validates the game config at `path`, printing any diagnostics.
+///
+/// Returns whether the config is free of errors (warnings are allowed).
+fn check(path: &Path) -> anyhow::Result<bool> {
+    let cfg = config::Game::load(path)?;
+    let diagnostics = cfg.validate();
+    for d in &diagnostics {
+        println!("{d}");
+    }
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity() == Severity::Error)
+        .count();
+    let warnings = diagnostics.len() - errors;
+    println!(
+        "{}: {errors} error(s), {warnings} warning(s)",
+        path.display()
+    );
+    Ok(errors == 0)
+}
+
+/// The games in the library, and their history in the database, as a source
+/// for the in-app picker.
+struct Catalogue {
+    lib: config::Library,
+    db: Rc<RefCell<db::Db>>,
+}
+
+impl pick::Source for Catalogue {
+    fn games(&mut self) -> Vec<pick::Choice> {
+        if let Err(e) = self.lib.refresh() {
+            eprintln!("warning: couldn't rescan game library: {e}");
+        }
+        self.lib
+            .games()
+            .map(|g| pick::Choice {
+                id: g.id.clone(),
+                name: g.name.clone(),
+            })
+            .collect()
+    }
+
+    fn categories(&mut self, game: &str) -> Vec<pick::Choice> {
+        let Ok(cfg) = find_game(&self.lib, game) else {
+            return vec![];
+        };
+        cfg.category_ids()
+            .into_iter()
+            .filter_map(|id| {
+                let name = cfg.category(&id).ok()?.name;
+                Some(pick::Choice { id, name })
+            })
+            .collect()
+    }
+
+    fn load(&mut self, game: &str, category: &str) -> Result<pick::Loaded, pick::Error> {
+        self.load_run(game, category)
+            .map_err(|e| pick::Error::Load(e.into()))
+    }
+
+    fn practice_stats(&mut self, game: &str, category: &str) -> practice::Stats {
+        self.db
+            .borrow()
+            .practice_stats(game, category)
+            .unwrap_or_else(|e| {
+                eprintln!("warning: couldn't load practice history: {e}");
+                practice::Stats::default()
+            })
+    }
+}
+
+impl Catalogue {
+    /// Loads a run of `category` of `game`, along with any checkpoint of an
+    /// interrupted attempt at it.
+    fn load_run(&mut self, game: &str, category: &str) -> anyhow::Result<pick::Loaded> {
+        let cfg = find_game(&self.lib, game)?;
+        let db = self.db.borrow();
+        let attempt = db.attempt_count(&cfg.id, category)?;
+        let mut run = cfg.to_run(category, attempt)?;
+        run.pb = db.pb(&cfg.id, category)?;
+        run.comparisons = db.comparisons(&run)?;
+        Ok(pick::Loaded {
+            run,
+            checkpoint: db.checkpoint(&cfg.id, category)?,
+        })
+    }
+}
+
+/// Prints a summary of the session to stdout when it ends.
+struct SummaryPrinter;
+
+impl session::Observer for SummaryPrinter {
+    fn on_session_end(&mut self, session: &session::Session) {
+        println!("Session over: {session}");
+    }
+}
@@ -1,7 +1,9 @@
 //! Configuring zombiesplit.
 
 pub mod game;
+pub mod library;
 pub mod system;
 
 pub use game::Game;
+pub use library::Library;
 pub use system::System;
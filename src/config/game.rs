@@ -1,23 +1,31 @@
 //! Configuration structs for games, split groups, splits, records, and categories.
 use crate::model::{
     run::{self, Metadata},
-    split, time,
+    split::Against,
+    time,
 };
 
 use serde::{Deserialize, Serialize};
-use serde_with::{DeserializeFromStr, SerializeDisplay};
 use std::{
     collections::HashMap,
-    fmt::{self, Display},
     io::Read,
-    path::Path,
-    str::FromStr,
+    path::{Path, PathBuf},
 };
 use thiserror::Error;
 
+pub mod category;
+pub mod validate;
+
+pub use crate::model::practice::IL_PREFIX;
+pub use category::Category;
+pub use validate::{Diagnostic, Problem, Severity};
+
 /// Configuration for a game.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Game {
+    /// The ID of the game, taken from the stem of the file it was loaded from.
+    #[serde(skip)]
+    pub id: GameId,
     /// The name of the game.
     pub name: String,
     /// Map of split groups for the game.
@@ -34,10 +42,13 @@ impl Game {
     /// Returns an error if `path` does not exist, is not readable, or does
     /// not contain valid TOML.
     pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
         let mut file = std::fs::File::open(path)?;
         let mut contents = String::new();
         file.read_to_string(&mut contents)?;
-        Ok(toml::from_str(&contents)?)
+        let mut game: Self = toml::from_str(&contents)?;
+        game.id = id_from_path(path);
+        Ok(game)
     }
 
     /// Creates a new run using the game as a template.
@@ -45,66 +56,49 @@ impl Game {
     /// This is a temporary function that will likely go away once we implement
     /// sqlite integration.
     ///
+    /// This only checks as much of the configuration as it needs; use
+    /// [`Game::validate`] to find every problem at once.
+    ///
     /// # Errors
     ///
-    /// Returns an error if the configuration references a category or group
-    /// that is not available elsewhere in the configuration.
-    pub fn to_run(&self, category: &str) -> Result<run::Run> {
-        let cat = self.expand_category(category)?;
-        // TODO(@MattWindsor91): check groups are valid
+    /// Returns an error if the configuration references a category, group,
+    /// or split that is not available elsewhere in the configuration.
+    pub fn to_run(&self, category: &str, attempt: usize) -> Result<run::Run> {
+        let cat = self.category(category)?;
+        let (splits, groups) = cat.to_splits(self)?;
 
         Ok(run::Run {
-            attempt: 0,
-            metadata: self.to_metadata(&cat),
-            splits: self.to_splits(&cat)?,
-            // TODO(@MattWindsor91): add comparisons
+            attempt,
+            started: None,
+            ended: None,
+            metadata: self.to_metadata(category, &cat.name),
+            splits,
+            groups,
+            // Comparisons come from the run history, not the config.
             comparisons: vec![],
+            against: Against::default(),
+            pb: None,
         })
     }
 
-    fn expand_category(&self, category: &str) -> Result<&Category> {
-        self.categories
-            .get(category)
-            .ok_or_else(|| Error::MissingCategory(category.to_owned()))
-    }
-
-    fn to_metadata(&self, category: &Category) -> run::Metadata {
+    fn to_metadata(&self, category_id: &str, category: &str) -> run::Metadata {
         Metadata {
+            game_id: self.id.clone(),
+            category_id: category_id.to_owned(),
             game: self.name.clone(),
-            category: category.name.clone(),
+            category: category.to_owned(),
         }
     }
-
-    fn to_splits(&self, category: &Category) -> Result<Vec<split::Split>> {
-        let mut splits = vec![];
-        for groupid in &category.groups {
-            let group = self
-                .groups
-                .get(groupid)
-                .ok_or_else(|| Error::MissingGroup(groupid.clone()))?;
-            splits.extend(
-                group
-                    .splits
-                    .iter()
-                    .map(|split| split::Split::new(&split.name)),
-            )
-        }
-        Ok(splits)
-    }
 }
 
-/// A run category.
-#[derive(Serialize, Deserialize, Debug)]
-pub struct Category {
-    /// The display name of the category.
-    pub name: String,
-    /// The list of groups that make up the category.
-    #[serde(default)]
-    pub groups: Vec<GroupId>,
+/// Derives a game ID from the file stem of `path`.
+fn id_from_path(path: &Path) -> GameId {
+    path.file_stem()
+        .map_or_else(String::new, |s| s.to_string_lossy().into_owned())
 }
 
 /// A configured split group.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Group {
     /// The name of the group.
     pub name: String,
@@ -113,34 +107,49 @@ pub struct Group {
 }
 
 /// A configured split.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Split {
     /// The split name.
     pub name: String,
+    /// An optional short name, used when there isn't space for the full name.
+    pub short: Option<String>,
+    /// An optional path to an icon for the split.
+    pub icon: Option<PathBuf>,
     /// The set of records configured for this split.
+    #[serde(default)]
     pub records: HashMap<CategoryId, Record>,
+    /// Any subsplits of this split.
+    ///
+    /// A split with subsplits is timed through its subsplits, and becomes a
+    /// group containing them.
+    #[serde(default)]
+    pub splits: Vec<Split>,
 }
 
 /// A configured record.
-#[derive(SerializeDisplay, DeserializeFromStr, Debug)]
+///
+/// The time is kept as written until it is needed, so that a malformed time
+/// shows up in validation rather than failing the whole load.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(transparent)]
 pub struct Record {
-    /// The time.
-    pub time: time::Time,
+    /// The time, as written in the config.
+    pub time: String,
 }
 
-impl Display for Record {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.time)
+impl Record {
+    /// Parses the time of this record.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the time is not a valid [`time::Time`].
+    pub fn parse_time(&self) -> std::result::Result<time::Time, time::Error> {
+        self.time.parse()
     }
 }
 
-impl FromStr for Record {
-    type Err = time::Error; // for now
-
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        Ok(Record { time: s.parse()? })
-    }
-}
+/// A game ID.
+pub type GameId = String;
 
 /// A group ID.
 pub type GroupId = String;
@@ -163,6 +172,14 @@ pub enum Error {
     /// Something referenced a missing group.
     #[error("Missing group: {0}")]
     MissingGroup(GroupId),
+
+    /// Something referenced a missing split.
+    #[error("Missing split: {0}")]
+    MissingSplit(String),
+
+    /// A category extends itself, directly or indirectly.
+    #[error("Category extends itself: {0}")]
+    CyclicCategory(CategoryId),
 }
 
 /// Shorthand for results over [Error].
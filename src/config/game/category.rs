@@ -0,0 +1,370 @@
+//! Run categories, and their resolution into lists of splits and groups.
+//!
+//! Categories refer to the game's splits through references of the form
+//! `group` or `group/split[/subsplit...]`, where each part after the group ID
+//! is the name of a split.  A category can extend another category, adding
+//! groups and individual splits to it and excluding others.
+//!
+//! Alongside the configured categories, every group and split has a generated
+//! individual-level (IL) category, whose ID is its reference prefixed with
+//! [`IL_PREFIX`] and a slash (for instance `il/pp/Palmtree Panic 1`).  A
+//! split's record keyed by [`IL_PREFIX`] alone is its IL record.
+//!
+//! References double as the IDs of the splits and groups in a run.
+
+use super::{CategoryId, Error, Game, GroupId, Result, Split};
+use crate::model::{
+    group,
+    practice::{self, IL_PREFIX},
+    split,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// A run category.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Category {
+    /// The display name of the category.
+    pub name: String,
+    /// The ID of a category whose splits this category starts from.
+    pub extends: Option<CategoryId>,
+    /// The list of groups that make up the category.
+    #[serde(default)]
+    pub groups: Vec<GroupId>,
+    /// References to individual splits to add after the groups.
+    #[serde(default)]
+    pub include: Vec<String>,
+    /// References to groups or splits to leave out, including any inherited
+    /// from the category this one extends.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+impl Category {
+    /// Gets whether this category, on its own, contributes no splits.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.extends.is_none() && self.groups.is_empty() && self.include.is_empty()
+    }
+}
+
+/// A category with its inheritance resolved.
+#[derive(Debug)]
+pub struct Resolved {
+    /// The display name of the category.
+    pub name: String,
+    /// References to the groups and splits in the category, in order.
+    pub entries: Vec<String>,
+    /// References to the groups and splits left out of the category.
+    pub exclude: HashSet<String>,
+}
+
+/// Something a reference can point to.
+#[derive(Copy, Clone, Debug)]
+pub(super) enum Item<'a> {
+    /// A group.
+    Group(&'a super::Group),
+    /// A split, possibly with subsplits.
+    Split(&'a Split),
+}
+
+impl<'a> Item<'a> {
+    /// Gets the name of the referenced item.
+    pub(super) fn name(self) -> &'a str {
+        match self {
+            Self::Group(g) => &g.name,
+            Self::Split(s) => &s.name,
+        }
+    }
+}
+
+impl Game {
+    /// Resolves the category with ID `id`, which may be a generated IL
+    /// category.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the category, or any category it extends, is missing or
+    /// extends itself; or if an IL category refers to a missing split.
+    pub fn category(&self, id: &str) -> Result<Resolved> {
+        if let Some(target) = il_target(id) {
+            let item = self
+                .find(target)
+                .ok_or_else(|| Error::MissingCategory(id.to_owned()))?;
+            return Ok(Resolved {
+                name: practice::il_category_name(item.name()),
+                entries: vec![target.to_owned()],
+                exclude: HashSet::new(),
+            });
+        }
+
+        let mut chain = vec![];
+        let mut current = id;
+        loop {
+            if chain.iter().any(|(cid, _)| *cid == current) {
+                return Err(Error::CyclicCategory(id.to_owned()));
+            }
+            let cat = self
+                .categories
+                .get(current)
+                .ok_or_else(|| Error::MissingCategory(current.to_owned()))?;
+            chain.push((current, cat));
+            match cat.extends {
+                Some(ref parent) => current = parent,
+                None => break,
+            }
+        }
+
+        let mut resolved = Resolved {
+            name: chain[0].1.name.clone(),
+            entries: vec![],
+            exclude: HashSet::new(),
+        };
+        for (_, cat) in chain.iter().rev() {
+            resolved.entries.extend(cat.groups.iter().cloned());
+            resolved.entries.extend(cat.include.iter().cloned());
+            resolved.exclude.extend(cat.exclude.iter().cloned());
+        }
+        Ok(resolved)
+    }
+
+    /// Lists the IDs of every category, configured and generated.
+    ///
+    /// Configured categories come first, sorted by ID, followed by the IL
+    /// categories in the order their groups and splits appear in the config.
+    #[must_use]
+    pub fn category_ids(&self) -> Vec<CategoryId> {
+        let mut ids: Vec<CategoryId> = self.categories.keys().cloned().collect();
+        ids.sort();
+
+        let mut groups: Vec<_> = self.groups.iter().collect();
+        groups.sort_by_key(|(id, _)| *id);
+        for (id, group) in groups {
+            ids.push(practice::il_category_id(id));
+            push_il_ids(&mut ids, id, &group.splits);
+        }
+        ids
+    }
+
+    /// Finds the group or split referenced by `reference`.
+    pub(super) fn find(&self, reference: &str) -> Option<Item<'_>> {
+        let mut parts = reference.split('/');
+        let group = self.groups.get(parts.next()?)?;
+        let mut item = Item::Group(group);
+        for name in parts {
+            let splits = match item {
+                Item::Group(g) => &g.splits,
+                Item::Split(s) => &s.splits,
+            };
+            item = Item::Split(splits.iter().find(|s| s.name == name)?);
+        }
+        Some(item)
+    }
+}
+
+/// Pushes the IL category IDs of `splits`, and their subsplits, whose parent
+/// has reference `parent`.
+fn push_il_ids(ids: &mut Vec<CategoryId>, parent: &str, splits: &[Split]) {
+    for split in splits {
+        let reference = format!("{parent}/{}", split.name);
+        ids.push(practice::il_category_id(&reference));
+        push_il_ids(ids, &reference, &split.splits);
+    }
+}
+
+/// If `id` is the ID of a generated IL category, gets the reference it
+/// targets.
+pub(super) fn il_target(id: &str) -> Option<&str> {
+    id.strip_prefix(IL_PREFIX)?.strip_prefix('/')
+}
+
+impl Resolved {
+    /// Expands this category into a flat list of splits, and the groups
+    /// spanning them.
+    ///
+    /// Groups left with no splits after exclusion are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Fails if an entry refers to a missing group or split.
+    pub fn to_splits(&self, game: &Game) -> Result<(Vec<split::Split>, Vec<group::Group>)> {
+        let mut e = Expander {
+            exclude: &self.exclude,
+            splits: vec![],
+            groups: vec![],
+        };
+        for entry in &self.entries {
+            if self.exclude.contains(entry) {
+                continue;
+            }
+            match game.find(entry) {
+                Some(Item::Group(g)) => e.group(entry, &g.name, &g.splits, 0),
+                Some(Item::Split(s)) => e.split(entry, s, 0),
+                None if entry.contains('/') => return Err(Error::MissingSplit(entry.clone())),
+                None => return Err(Error::MissingGroup(entry.clone())),
+            }
+        }
+        Ok((e.splits, e.groups))
+    }
+}
+
+/// Holds state while expanding a category into splits and groups.
+struct Expander<'a> {
+    exclude: &'a HashSet<String>,
+    splits: Vec<split::Split>,
+    groups: Vec<group::Group>,
+}
+
+impl Expander<'_> {
+    /// Expands a group, or a split with subsplits, with reference `path`.
+    fn group(&mut self, path: &str, name: &str, splits: &[Split], depth: usize) {
+        let index = self.groups.len();
+        let start = self.splits.len();
+        self.groups.push(group::Group {
+            id: path.to_owned(),
+            name: name.to_owned(),
+            start,
+            len: 0,
+            depth,
+        });
+
+        for sub in splits {
+            let path = format!("{path}/{}", sub.name);
+            if !self.exclude.contains(&path) {
+                self.split(&path, sub, depth + 1);
+            }
+        }
+
+        let len = self.splits.len() - start;
+        if len == 0 {
+            // Any groups pushed since are nested inside this one, so are also
+            // empty.
+            self.groups.truncate(index);
+        } else {
+            self.groups[index].len = len;
+        }
+    }
+
+    /// Expands a split with reference `path` at group depth `depth`.
+    fn split(&mut self, path: &str, split: &Split, depth: usize) {
+        if !split.splits.is_empty() {
+            self.group(path, &split.name, &split.splits, depth);
+            return;
+        }
+
+        let mut s = split::Split::new(&split.name);
+        path.clone_into(&mut s.id);
+        s.short.clone_from(&split.short);
+        s.icon.clone_from(&split.icon);
+        self.splits.push(s);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAME: &str = r#"
+        name = "Game"
+        [groups.a]
+        name = "A"
+        splits = [
+            { name = "A1" },
+            { name = "A2", splits = [{ name = "Act 1" }, { name = "Act 2" }] },
+        ]
+        [groups.b]
+        name = "B"
+        splits = [{ name = "B1" }, { name = "B2" }]
+        [categories.any]
+        name = "Any%"
+        groups = ["a"]
+        [categories.more]
+        name = "More"
+        extends = "any"
+        include = ["b/B2"]
+        exclude = ["a/A2/Act 1"]
+        [categories.less]
+        name = "Less"
+        extends = "more"
+        exclude = ["a/A2/Act 2"]
+        [categories.loop]
+        name = "Loop"
+        extends = "loop"
+    "#;
+
+    fn game() -> Game {
+        toml::from_str(GAME).expect("test config should parse")
+    }
+
+    /// A group as (name, start, len, depth).
+    type GroupTuple = (String, usize, usize, usize);
+
+    /// Expands category `id` into its split names and group tuples.
+    fn expand(id: &str) -> (Vec<String>, Vec<GroupTuple>) {
+        let g = game();
+        let (splits, groups) = g.category(id).unwrap().to_splits(&g).unwrap();
+        (
+            splits.into_iter().map(|s| s.name).collect(),
+            groups
+                .into_iter()
+                .map(|g| (g.name, g.start, g.len, g.depth))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn category_extends_includes_and_excludes() {
+        let (splits, groups) = expand("more");
+        assert_eq!(splits, vec!["A1", "Act 2", "B2"]);
+        assert_eq!(
+            groups,
+            vec![("A".to_owned(), 0, 2, 0), ("A2".to_owned(), 1, 1, 1)]
+        );
+        assert_eq!(game().category("more").unwrap().name, "More");
+    }
+
+    #[test]
+    fn category_drops_emptied_groups() {
+        let (splits, groups) = expand("less");
+        assert_eq!(splits, vec!["A1", "B2"]);
+        assert_eq!(groups, vec![("A".to_owned(), 0, 1, 0)]);
+    }
+
+    #[test]
+    fn category_il() {
+        let (splits, groups) = expand("il/a/A2");
+        assert_eq!(splits, vec!["Act 1", "Act 2"]);
+        assert_eq!(groups, vec![("A2".to_owned(), 0, 2, 0)]);
+        assert_eq!(game().category("il/b/B1").unwrap().name, "B1 (IL)");
+        assert!(game().category("il/b/B3").is_err());
+    }
+
+    #[test]
+    fn category_cycle() {
+        assert!(matches!(
+            game().category("loop"),
+            Err(Error::CyclicCategory(_))
+        ));
+    }
+
+    #[test]
+    fn category_ids_include_ils() {
+        assert_eq!(
+            game().category_ids(),
+            vec![
+                "any",
+                "less",
+                "loop",
+                "more",
+                "il/a",
+                "il/a/A1",
+                "il/a/A2",
+                "il/a/A2/Act 1",
+                "il/a/A2/Act 2",
+                "il/b",
+                "il/b/B1",
+                "il/b/B2",
+            ]
+        );
+    }
+}
@@ -0,0 +1,383 @@
+//! Validation of game configuration.
+//!
+//! Loading and [`Game::to_run`] stop at the first problem they meet, and only
+//! check the parts of the config they use.  Validation instead walks the whole
+//! config and reports every problem it finds, each tagged with the TOML path
+//! at which it occurs.
+
+use super::{
+    category::{self, Category},
+    CategoryId, Game, GroupId, Split, IL_PREFIX,
+};
+use crate::model::time;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Display},
+};
+use thiserror::Error;
+
+impl Game {
+    /// Validates this game config, returning every problem found.
+    ///
+    /// Diagnostics appear in a stable order: categories first, then groups,
+    /// each sorted by ID.
+    #[must_use]
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut v = Validator {
+            game: self,
+            diagnostics: vec![],
+        };
+        v.categories();
+        v.groups();
+        v.diagnostics
+    }
+}
+
+/// A problem found at a particular place in a game config.
+#[derive(Debug)]
+pub struct Diagnostic {
+    /// The TOML path of the problem, for instance `categories.btg.groups[2]`.
+    pub path: String,
+    /// The problem itself.
+    pub problem: Problem,
+}
+
+impl Diagnostic {
+    /// Gets the severity of this diagnostic.
+    #[must_use]
+    pub fn severity(&self) -> Severity {
+        self.problem.severity()
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.severity(), self.path, self.problem)
+    }
+}
+
+/// How bad a problem is.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The config works, but is probably not what was intended.
+    Warning,
+    /// The config is broken.
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Warning => "warning",
+            Self::Error => "error",
+        })
+    }
+}
+
+/// Enumeration of problems that validation can find.
+#[derive(Debug, Error)]
+pub enum Problem {
+    /// A category references a group that doesn't exist.
+    #[error("unknown group '{0}'")]
+    UnknownGroup(GroupId),
+    /// A category has no groups or splits, and extends no other category.
+    #[error("category has no groups or splits")]
+    EmptyCategory,
+    /// A category extends itself, directly or indirectly.
+    #[error("category extends itself")]
+    CyclicCategory,
+    /// A category's ID clashes with those of generated IL categories.
+    #[error("category ID is reserved for individual-level categories")]
+    ReservedCategory,
+    /// A reference doesn't point to any group or split.
+    #[error("unknown group or split '{0}'")]
+    UnknownSplit(String),
+    /// A split has the same name as an earlier split with the same parent.
+    #[error("duplicate split name '{name}' (first used at {first})")]
+    DuplicateSplit {
+        /// The duplicated name.
+        name: String,
+        /// The TOML path of the first split with this name.
+        first: String,
+    },
+    /// A record, or an extending category, refers to a category that doesn't
+    /// exist.
+    #[error("unknown category '{0}'")]
+    UnknownCategory(CategoryId),
+    /// A record's time doesn't parse.
+    #[error("unparsable record time: {0}")]
+    BadTime(#[source] time::Error),
+    /// A group isn't used by any category.
+    #[error("group is not used by any category")]
+    UnusedGroup,
+}
+
+impl Problem {
+    /// Gets the severity of this problem.
+    #[must_use]
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::EmptyCategory | Self::UnusedGroup => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+/// Holds state for a validation pass.
+struct Validator<'a> {
+    game: &'a Game,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Validator<'_> {
+    fn report(&mut self, path: String, problem: Problem) {
+        self.diagnostics.push(Diagnostic { path, problem });
+    }
+
+    fn categories(&mut self) {
+        for (id, cat) in sorted(&self.game.categories) {
+            let path = format!("categories.{}", key(id));
+            if id == IL_PREFIX || category::il_target(id).is_some() {
+                self.report(path.clone(), Problem::ReservedCategory);
+            }
+            if cat.is_empty() {
+                self.report(path.clone(), Problem::EmptyCategory);
+            }
+            self.extends(&path, id, cat);
+            for (i, group) in cat.groups.iter().enumerate() {
+                if !self.game.groups.contains_key(group) {
+                    self.report(
+                        format!("{path}.groups[{i}]"),
+                        Problem::UnknownGroup(group.clone()),
+                    );
+                }
+            }
+            self.references(&format!("{path}.include"), &cat.include);
+            self.references(&format!("{path}.exclude"), &cat.exclude);
+        }
+    }
+
+    /// Checks that the category `id`, at `path`, extends a category that
+    /// exists and doesn't lead back to `id`.
+    ///
+    /// Cycles are only reported on the categories that form them, not those
+    /// that extend into them.
+    fn extends(&mut self, path: &str, id: &str, cat: &Category) {
+        let Some(ref parent) = cat.extends else {
+            return;
+        };
+        let path = format!("{path}.extends");
+        if !self.game.categories.contains_key(parent) {
+            self.report(path, Problem::UnknownCategory(parent.clone()));
+            return;
+        }
+
+        let mut seen = HashSet::new();
+        let mut current = parent;
+        while seen.insert(current) {
+            if current == id {
+                self.report(path, Problem::CyclicCategory);
+                return;
+            }
+            match self
+                .game
+                .categories
+                .get(current)
+                .and_then(|c| c.extends.as_ref())
+            {
+                Some(next) => current = next,
+                None => return,
+            }
+        }
+    }
+
+    fn references(&mut self, path: &str, refs: &[String]) {
+        for (i, r) in refs.iter().enumerate() {
+            if self.game.find(r).is_none() {
+                self.report(format!("{path}[{i}]"), Problem::UnknownSplit(r.clone()));
+            }
+        }
+    }
+
+    fn groups(&mut self) {
+        let used: HashSet<&str> = self
+            .game
+            .categories
+            .values()
+            .flat_map(|c| c.groups.iter().chain(c.include.iter()))
+            .filter_map(|r| r.split('/').next())
+            .collect();
+
+        for (id, group) in sorted(&self.game.groups) {
+            let path = format!("groups.{}", key(id));
+            if !used.contains(id.as_str()) {
+                self.report(path.clone(), Problem::UnusedGroup);
+            }
+            self.splits(&format!("{path}.splits"), &group.splits);
+        }
+    }
+
+    /// Validates a list of sibling splits at `path`.
+    ///
+    /// Split names need only be unique among siblings: subsplits such as
+    /// 'Act 1' can recur under different parents.
+    fn splits(&mut self, path: &str, splits: &[Split]) {
+        let mut seen: HashMap<&str, String> = HashMap::new();
+        for (i, split) in splits.iter().enumerate() {
+            let path = format!("{path}[{i}]");
+            if let Some(first) = seen.get(split.name.as_str()) {
+                let problem = Problem::DuplicateSplit {
+                    name: split.name.clone(),
+                    first: first.clone(),
+                };
+                self.report(path.clone(), problem);
+            } else {
+                seen.insert(&split.name, path.clone());
+            }
+            self.records(&path, split);
+            self.splits(&format!("{path}.splits"), &split.splits);
+        }
+    }
+
+    fn records(&mut self, path: &str, split: &Split) {
+        for (cat, record) in sorted(&split.records) {
+            let path = format!("{path}.records.{}", key(cat));
+            if !self.is_record_category(cat) {
+                self.report(path.clone(), Problem::UnknownCategory(cat.clone()));
+            }
+            if let Err(e) = record.parse_time() {
+                self.report(path, Problem::BadTime(e));
+            }
+        }
+    }
+
+    /// Gets whether a record can be keyed by `id`: either a configured
+    /// category, an IL category, or [`IL_PREFIX`] alone for the split's own IL.
+    fn is_record_category(&self, id: &str) -> bool {
+        id == IL_PREFIX
+            || self.game.categories.contains_key(id)
+            || category::il_target(id).is_some_and(|t| self.game.find(t).is_some())
+    }
+}
+
+/// Gets the entries of `map` sorted by key.
+fn sorted<V>(map: &HashMap<String, V>) -> Vec<(&String, &V)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_by_key(|(k, _)| *k);
+    entries
+}
+
+/// Formats `k` as a TOML key, quoting it if it isn't a bare key.
+fn key(k: &str) -> String {
+    let bare = !k.is_empty()
+        && k.chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare {
+        k.to_owned()
+    } else {
+        format!("{k:?}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnose(toml: &str) -> Vec<String> {
+        let game: Game = toml::from_str(toml).expect("test config should parse");
+        game.validate().iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn validate_clean() {
+        let diags = diagnose(
+            r#"
+            name = "Game"
+            [groups.a]
+            name = "A"
+            splits = [{ name = "A1", records.any = "1m2s" }]
+            [categories.any]
+            name = "Any%"
+            groups = ["a"]
+            "#,
+        );
+        assert!(diags.is_empty(), "unexpected diagnostics: {:?}", diags);
+    }
+
+    #[test]
+    fn validate_reports_everything() {
+        let diags = diagnose(
+            r#"
+            name = "Game"
+            [groups.a]
+            name = "A"
+            splits = [
+                { name = "A1", records.nope = "1m" },
+                { name = "A1", records.any = "1x" },
+                { name = "A2", splits = [{ name = "S" }, { name = "S" }] },
+            ]
+            [groups.b]
+            name = "B"
+            splits = []
+            [categories.any]
+            name = "Any%"
+            groups = ["a", "c"]
+            [categories."100%"]
+            name = "100%"
+            "#,
+        );
+        assert_eq!(
+            diags,
+            vec![
+                "warning: categories.\"100%\": category has no groups or splits",
+                "error: categories.any.groups[1]: unknown group 'c'",
+                "error: groups.a.splits[0].records.nope: unknown category 'nope'",
+                "error: groups.a.splits[1]: duplicate split name 'A1' (first used at groups.a.splits[0])",
+                "error: groups.a.splits[1].records.any: unparsable record time: field milliseconds failed parsing: invalid digit found in string",
+                "error: groups.a.splits[2].splits[1]: duplicate split name 'S' (first used at groups.a.splits[2].splits[0])",
+                "warning: groups.b: group is not used by any category",
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_categories() {
+        let diags = diagnose(
+            r#"
+            name = "Game"
+            [groups.a]
+            name = "A"
+            splits = [{ name = "A1", records.il = "1s", records."il/a" = "2s", records."il/c" = "3s" }]
+            [groups.b]
+            name = "B"
+            splits = [{ name = "B1" }]
+            [categories.x]
+            name = "X"
+            extends = "y"
+            include = ["b/B1", "b/B2"]
+            [categories.y]
+            name = "Y"
+            extends = "x"
+            exclude = ["c"]
+            [categories.z]
+            name = "Z"
+            extends = "w"
+            [categories."il/a"]
+            name = "Clash"
+            groups = ["a"]
+            "#,
+        );
+        assert_eq!(
+            diags,
+            vec![
+                "error: categories.\"il/a\": category ID is reserved for individual-level categories",
+                "error: categories.x.extends: category extends itself",
+                "error: categories.x.include[1]: unknown group or split 'b/B2'",
+                "error: categories.y.extends: category extends itself",
+                "error: categories.y.exclude[0]: unknown group or split 'c'",
+                "error: categories.z.extends: unknown category 'w'",
+                "error: groups.a.splits[0].records.\"il/c\": unknown category 'il/c'",
+            ]
+        );
+    }
+}
@@ -0,0 +1,174 @@
+//! Discovery of game configs from a library directory.
+//!
+//! A library is a directory of game config TOML files, one per game, indexed
+//! by game ID (the file stem).  Libraries can watch their directory, and
+//! rescan it when asked if anything has changed.
+
+use super::game::{self, Game, GameId};
+use notify::{DebouncedEvent, RecommendedWatcher, RecursiveMode, Watcher};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::Duration,
+};
+use thiserror::Error;
+
+/// How long to wait for file changes to settle before reporting them.
+const WATCH_DELAY: Duration = Duration::from_millis(500);
+
+/// A library of game configs.
+pub struct Library {
+    /// The directory holding the library.
+    path: PathBuf,
+    /// The games found in the library, by ID.
+    games: BTreeMap<GameId, Game>,
+    /// Files in the library that failed to load, and why.
+    failures: Vec<(PathBuf, game::Error)>,
+    /// The watch on the library directory, if any.
+    watch: Option<Watch>,
+}
+
+impl Library {
+    /// Opens the library at `path`, scanning it for games.
+    ///
+    /// A missing directory is treated as an empty library.  Individual game
+    /// configs that fail to load don't fail the scan, but are recorded in
+    /// [`Library::failures`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` exists but can't be read as a directory.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let mut lib = Self {
+            path: path.as_ref().to_owned(),
+            games: BTreeMap::new(),
+            failures: vec![],
+            watch: None,
+        };
+        lib.scan()?;
+        Ok(lib)
+    }
+
+    /// Gets the path of the library directory.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Iterates over the games in the library, in order of ID.
+    pub fn games(&self) -> impl Iterator<Item = &Game> {
+        self.games.values()
+    }
+
+    /// Gets the game with ID `id`, if it is in the library.
+    #[must_use]
+    pub fn game(&self, id: &str) -> Option<&Game> {
+        self.games.get(id)
+    }
+
+    /// Gets the files that failed to load in the last scan, along with why.
+    #[must_use]
+    pub fn failures(&self) -> &[(PathBuf, game::Error)] {
+        &self.failures
+    }
+
+    /// Rescans the library directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the library directory exists but can't be read.
+    pub fn scan(&mut self) -> Result<()> {
+        self.games.clear();
+        self.failures.clear();
+        if !self.path.exists() {
+            return Ok(());
+        }
+        for entry in std::fs::read_dir(&self.path)? {
+            let path = entry?.path();
+            if !is_game_config(&path) {
+                continue;
+            }
+            match Game::load(&path) {
+                Ok(game) => {
+                    self.games.insert(game.id.clone(), game);
+                }
+                Err(e) => self.failures.push((path, e)),
+            }
+        }
+        self.failures.sort_by(|(p1, _), (p2, _)| p1.cmp(p2));
+        Ok(())
+    }
+
+    /// Starts watching the library directory for changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the platform can't watch the directory (for
+    /// instance, if it doesn't exist).
+    pub fn watch(&mut self) -> Result<()> {
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::watcher(tx, WATCH_DELAY)?;
+        watcher.watch(&self.path, RecursiveMode::NonRecursive)?;
+        self.watch = Some(Watch {
+            _watcher: watcher,
+            rx,
+        });
+        Ok(())
+    }
+
+    /// Rescans the library if the watch has seen any changes since last
+    /// time; returns whether it did.
+    ///
+    /// This never rescans if the library isn't being watched.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rescan fails.
+    pub fn refresh(&mut self) -> Result<bool> {
+        let changed = self.watch.as_ref().is_some_and(Watch::has_changed);
+        if changed {
+            self.scan()?;
+        }
+        Ok(changed)
+    }
+}
+
+/// Gets whether `path` looks like a game config.
+fn is_game_config(path: &Path) -> bool {
+    path.is_file() && path.extension().is_some_and(|e| e == "toml")
+}
+
+/// A watch on a library directory.
+struct Watch {
+    /// The watcher, which stops watching when dropped.
+    _watcher: RecommendedWatcher,
+    /// The channel on which the watcher reports changes.
+    rx: mpsc::Receiver<DebouncedEvent>,
+}
+
+impl Watch {
+    /// Drains any pending events, and gets whether any of them were changes.
+    fn has_changed(&self) -> bool {
+        let mut changed = false;
+        while let Ok(event) = self.rx.try_recv() {
+            changed |= !matches!(
+                event,
+                DebouncedEvent::NoticeWrite(_) | DebouncedEvent::NoticeRemove(_)
+            );
+        }
+        changed
+    }
+}
+
+/// Enumeration of errors occurring when using a library.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("IO error reading game library")]
+    Io(#[from] std::io::Error),
+    #[error("couldn't watch game library")]
+    Watch(#[from] notify::Error),
+}
+
+/// Shorthand for results over [Error].
+pub type Result<T> = std::result::Result<T, Error>;
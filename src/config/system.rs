@@ -2,14 +2,35 @@
 
 use crate::view;
 use serde::{Deserialize, Serialize};
-use std::{io::Read, path::Path};
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+};
 use thiserror::Error;
 
 /// System configuration for zombiesplit.
 #[derive(Serialize, Deserialize, Debug)]
 pub struct System {
+    /// Path to the zombiesplit database.
+    #[serde(default = "default_db_path")]
+    pub db_path: PathBuf,
+    /// Path to the directory holding the game library.
+    ///
+    /// Defaults to a `games` directory inside zombiesplit's data directory
+    /// (on Linux, following the XDG base directory specification).
+    #[serde(default = "default_library_path")]
+    pub library_path: PathBuf,
     /// UI configuration.
     pub ui: view::Config,
+    /// Keybindings, overriding the defaults.
+    ///
+    /// Conflicting bindings are rejected when the config is loaded.
+    #[serde(default)]
+    pub keys: view::keys::Config,
+    /// Game controller bindings, if wanted.
+    pub gamepad: Option<view::gamepad::Config>,
+    /// Global hotkeys, if wanted.
+    pub hotkeys: Option<view::hotkeys::Config>,
 }
 
 impl System {
@@ -25,16 +46,93 @@ impl System {
         file.read_to_string(&mut contents)?;
         Ok(toml::from_str(&contents)?)
     }
+
+    /// Remembers the window size `w` by `h` in the system config at `path`.
+    ///
+    /// This edits the `win_w` and `win_h` values of the `[ui.window]` table
+    /// in place, so that the rest of the file (including its comments and
+    /// layout) stays as it was.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` can't be read or written, isn't valid
+    /// TOML, or has no `win_w` or `win_h` in its `[ui.window]` table.
+    pub fn save_window_size<P: AsRef<Path>>(path: P, w: u32, h: u32) -> Result<()> {
+        let contents = std::fs::read_to_string(&path)?;
+        std::fs::write(&path, with_window_size(&contents, w, h)?)?;
+        Ok(())
+    }
+}
+
+/// Rewrites the window size in the system config `contents` to `w` by `h`.
+fn with_window_size(contents: &str, w: u32, h: u32) -> Result<String> {
+    let mut doc: toml_edit::DocumentMut = contents.parse()?;
+    for (key, size) in [("win_w", w), ("win_h", h)] {
+        let value = doc
+            .get_mut("ui")
+            .and_then(|ui| ui.get_mut("window"))
+            .and_then(|window| window.get_mut(key))
+            .and_then(toml_edit::Item::as_value_mut)
+            .ok_or(Error::MissingWindowSize(key))?;
+        // Keep any comments and spacing around the old value.
+        let decor = value.decor().clone();
+        *value = i64::from(size).into();
+        *value.decor_mut() = decor;
+    }
+    let mut out = doc.to_string();
+    // toml_edit ends the last line of a table, even if the file didn't.
+    if !contents.ends_with('\n') && out.ends_with('\n') {
+        out.pop();
+    }
+    Ok(out)
+}
+
+fn default_db_path() -> PathBuf {
+    PathBuf::from("zombiesplit.db")
+}
+
+fn default_library_path() -> PathBuf {
+    dirs::data_dir().map_or_else(
+        || PathBuf::from("games"),
+        |d| d.join("zombiesplit").join("games"),
+    )
 }
 
 /// Enumeration of errors occurring when interpreting system config.
 #[derive(Debug, Error)]
 pub enum Error {
-    #[error("IO error reading system config")]
+    #[error("IO error reading or writing system config")]
     Io(#[from] std::io::Error),
     #[error("Error parsing system config from TOML")]
     Toml(#[from] toml::de::Error),
+    #[error("Error parsing system config for editing")]
+    TomlEdit(#[from] toml_edit::TomlError),
+    #[error("No {0} in the [ui.window] table of the system config")]
+    MissingWindowSize(&'static str),
 }
 
 /// Shorthand for results over [Error].
 type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that remembering the window size only touches the window size.
+    #[test]
+    fn window_size() {
+        let before = "[ui.splits]\n\twin_w = 1\n\n[ui.window]\n\t# Width.\n\twin_w = 320 # px\n\twin_h = 640\n\tpadding = 4";
+        let after = "[ui.splits]\n\twin_w = 1\n\n[ui.window]\n\t# Width.\n\twin_w = 480 # px\n\twin_h = 270\n\tpadding = 4";
+        assert_eq!(with_window_size(before, 480, 270).unwrap(), after);
+        assert_eq!(
+            with_window_size(&format!("{before}\n"), 480, 270).unwrap(),
+            format!("{after}\n")
+        );
+
+        let missing = "[ui.window]\n\twin_w = 320\n";
+        assert!(matches!(
+            with_window_size(missing, 480, 270),
+            Err(Error::MissingWindowSize("win_h"))
+        ));
+    }
+}
@@ -0,0 +1,462 @@
+//! Persistence of zombiesplit data to a local database.
+
+use crate::model::{
+    checkpoint::{self, Checkpoint},
+    practice::{self, Attempt},
+    run::{Metadata, Outcome, Run},
+    session::{self, Session},
+    split::{Comparison, Status},
+    time::{self, Time},
+};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::{collections::HashMap, convert::TryFrom, path::Path};
+use thiserror::Error;
+
+/// The schema, applied every time the database is opened.
+const SCHEMA: &str = include_str!("db/schema.sql");
+
+/// A handle to the zombiesplit database.
+pub struct Db {
+    conn: Connection,
+}
+
+impl Db {
+    /// Opens the database at `path`, creating and initialising it if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if we can't open the database at `path`, or can't
+    /// apply the schema.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let db = Self {
+            conn: Connection::open(path)?,
+        };
+        db.init()?;
+        Ok(db)
+    }
+
+    /// Applies the schema to the database.
+    fn init(&self) -> Result<()> {
+        Ok(self.conn.execute_batch(SCHEMA)?)
+    }
+
+    /// Records a (presumably finished) session in the database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database fails to insert the session.
+    pub fn add_session(&self, s: &Session) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO session (game, category, start, end, attempts, completions, best)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                s.game_id,
+                s.category_id,
+                s.start,
+                s.end,
+                sat_i64(s.attempts),
+                sat_i64(s.completions),
+                s.best.map(u32::from),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Gets the number of the most recent attempt at the game with ID `game`
+    /// and category with ID `category`, or 0 if there have been no attempts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database fails to look up the attempt.
+    pub fn attempt_count(&self, game: &str, category: &str) -> Result<usize> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COALESCE(MAX(attempt), 0) FROM run WHERE game = ?1 AND category = ?2",
+            params![game, category],
+            |row| row.get(0),
+        )?;
+        Ok(usize::try_from(count).unwrap_or_default())
+    }
+
+    /// Gets the best completed time for the game with ID `game` and category
+    /// with ID `category`, if there is one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database fails to look up the time, or holds
+    /// a malformed time.
+    pub fn pb(&self, game: &str, category: &str) -> Result<Option<Time>> {
+        let best: Option<u32> = self.conn.query_row(
+            "SELECT MIN(total) FROM run
+             WHERE game = ?1 AND category = ?2 AND reset_at IS NULL",
+            params![game, category],
+            |row| row.get(0),
+        )?;
+        Ok(best.map(Time::try_from).transpose()?)
+    }
+
+    /// Gets comparisons for each split of `run`, from the history of its
+    /// game and category.
+    ///
+    /// Each split is compared against its best time in any run or in any
+    /// practice of it on its own, and its time in the personal best run.
+    /// Splits are matched to their history by ID, not position.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database fails to look up the split times.
+    pub fn comparisons(&self, run: &Run) -> Result<Vec<Comparison>> {
+        let (game, category) = (&run.metadata.game_id, &run.metadata.category_id);
+        let mut cmps = Vec::with_capacity(run.splits.len());
+        for s in &run.splits {
+            let il = practice::il_category_id(&s.id);
+            cmps.push(Comparison {
+                split: self.practice_stats(game, &il)?.best,
+                in_run: None,
+            });
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT split, MIN(time) FROM run_split JOIN run USING (runid)
+             WHERE game = ?1 AND category = ?2 GROUP BY split",
+        )?;
+        let bests = split_times(stmt.query(params![game, category])?)?;
+        for (s, c) in run.splits.iter().zip(&mut cmps) {
+            if let Some(&time) = bests.get(&s.id) {
+                c.split = Some(c.split.map_or(time, |best| best.min(time)));
+            }
+        }
+
+        let mut stmt = self.conn.prepare(
+            "SELECT split, time FROM run_split
+             WHERE runid = (SELECT runid FROM run
+                            WHERE game = ?1 AND category = ?2 AND reset_at IS NULL
+                            ORDER BY total, runid LIMIT 1)",
+        )?;
+        let pb = split_times(stmt.query(params![game, category])?)?;
+        for (s, c) in run.splits.iter().zip(&mut cmps) {
+            c.in_run = pb.get(&s.id).copied();
+        }
+        Ok(cmps)
+    }
+
+    /// Records a finished run, with outcome `outcome`, in the database.
+    ///
+    /// Only the times of splits that were timed, rather than skipped, are
+    /// kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the run hasn't both started and ended, or if the
+    /// database fails to insert the run.
+    pub fn add_run(&mut self, run: &Run, outcome: Outcome) -> Result<()> {
+        let (start, end) = run.started.zip(run.ended).ok_or(Error::RunNotFinished)?;
+        let reset_at = match outcome {
+            Outcome::Completed => None,
+            Outcome::Reset(split) => Some(sat_i64(split)),
+        };
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "INSERT INTO run (game, category, attempt, start, end, reset_at, total)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                run.metadata.game_id,
+                run.metadata.category_id,
+                sat_i64(run.attempt),
+                start,
+                end,
+                reset_at,
+                u32::from(run.total()),
+            ],
+        )?;
+        let id = tx.last_insert_rowid();
+
+        {
+            let mut stmt =
+                tx.prepare("INSERT INTO run_split (runid, split, time) VALUES (?1, ?2, ?3)")?;
+            for s in &run.splits {
+                if s.status() == Status::Completed {
+                    stmt.execute(params![id, s.id, u32::from(s.summed_time())])?;
+                }
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Records a practice attempt in the database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database fails to insert the attempt.
+    pub fn add_practice(&self, attempt: &Attempt) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO practice (game, category, start, end, total)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                attempt.game_id,
+                attempt.category_id,
+                attempt.started,
+                attempt.ended,
+                attempt.total.map(u32::from),
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Gets the all-time practice statistics for the game with ID `game` and
+    /// IL category with ID `category`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database fails to look up the statistics, or
+    /// holds a malformed best time.
+    pub fn practice_stats(&self, game: &str, category: &str) -> Result<practice::Stats> {
+        let (attempts, completions, best): (i64, i64, Option<u32>) = self.conn.query_row(
+            "SELECT COUNT(*), COUNT(total), MIN(total)
+             FROM practice WHERE game = ?1 AND category = ?2",
+            params![game, category],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+        Ok(practice::Stats {
+            attempts: usize::try_from(attempts).unwrap_or_default(),
+            completions: usize::try_from(completions).unwrap_or_default(),
+            best: best.map(Time::try_from).transpose()?,
+        })
+    }
+
+    /// Saves `checkpoint` as the checkpoint for the game and category in
+    /// `meta`, replacing any existing checkpoint.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database fails to store the checkpoint.
+    pub fn save_checkpoint(&mut self, meta: &Metadata, checkpoint: &Checkpoint) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        clear_checkpoint(&tx, meta)?;
+
+        let editor = checkpoint.editor.as_ref();
+        let field = editor.and_then(|e| e.field.as_ref());
+        tx.execute(
+            "INSERT INTO checkpoint
+                (game, category, attempt, start, cursor, editor_time, editor_field, editor_digits)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                meta.game_id,
+                meta.category_id,
+                sat_i64(checkpoint.attempt),
+                checkpoint.started,
+                sat_i64(checkpoint.cursor),
+                editor.map(|e| u32::from(e.time)),
+                field.map(|f| f.position.to_string()),
+                field.map(|f| &f.digits),
+            ],
+        )?;
+
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO checkpoint_time (game, category, split, time)
+                 VALUES (?1, ?2, ?3, ?4)",
+            )?;
+            for (split, times) in &checkpoint.times {
+                for time in times {
+                    stmt.execute(params![
+                        meta.game_id,
+                        meta.category_id,
+                        split,
+                        u32::from(*time)
+                    ])?;
+                }
+            }
+
+            let mut stmt = tx.prepare(
+                "INSERT INTO checkpoint_skip (game, category, split) VALUES (?1, ?2, ?3)",
+            )?;
+            for split in &checkpoint.skipped {
+                stmt.execute(params![meta.game_id, meta.category_id, split])?;
+            }
+        }
+
+        Ok(tx.commit()?)
+    }
+
+    /// Loads the checkpoint for the game with ID `game` and category with ID
+    /// `category`, if there is one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database fails to load the checkpoint, or the
+    /// checkpoint is malformed.
+    pub fn checkpoint(&self, game: &str, category: &str) -> Result<Option<Checkpoint>> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT attempt, start, cursor, editor_time, editor_field, editor_digits
+                 FROM checkpoint WHERE game = ?1 AND category = ?2",
+                params![game, category],
+                |row| {
+                    Ok(RawCheckpoint {
+                        attempt: row.get(0)?,
+                        started: row.get(1)?,
+                        cursor: row.get(2)?,
+                        editor_time: row.get(3)?,
+                        editor_field: row.get(4)?,
+                        editor_digits: row.get(5)?,
+                    })
+                },
+            )
+            .optional()?;
+        row.map(|raw| {
+            raw.cook(
+                self.checkpoint_times(game, category)?,
+                self.checkpoint_skips(game, category)?,
+            )
+        })
+        .transpose()
+    }
+
+    fn checkpoint_skips(&self, game: &str, category: &str) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT split FROM checkpoint_skip
+             WHERE game = ?1 AND category = ?2 ORDER BY rowid",
+        )?;
+        let rows = stmt.query_map(params![game, category], |row| row.get(0))?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    fn checkpoint_times(&self, game: &str, category: &str) -> Result<HashMap<String, Vec<Time>>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT split, time FROM checkpoint_time
+             WHERE game = ?1 AND category = ?2 ORDER BY rowid",
+        )?;
+        let rows = stmt.query_map(params![game, category], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, u32>(1)?))
+        })?;
+
+        let mut times: HashMap<String, Vec<Time>> = HashMap::new();
+        for row in rows {
+            let (split, time) = row?;
+            times.entry(split).or_default().push(Time::try_from(time)?);
+        }
+        Ok(times)
+    }
+}
+
+/// Reads split IDs and times from `rows` into a map.
+fn split_times(mut rows: rusqlite::Rows) -> Result<HashMap<String, Time>> {
+    let mut times = HashMap::new();
+    while let Some(row) = rows.next()? {
+        times.insert(row.get(0)?, Time::try_from(row.get::<_, u32>(1)?)?);
+    }
+    Ok(times)
+}
+
+/// Removes any checkpoint for the game and category in `meta`.
+fn clear_checkpoint(conn: &Connection, meta: &Metadata) -> Result<()> {
+    let params = params![meta.game_id, meta.category_id];
+    conn.execute(
+        "DELETE FROM checkpoint_time WHERE game = ?1 AND category = ?2",
+        params,
+    )?;
+    conn.execute(
+        "DELETE FROM checkpoint_skip WHERE game = ?1 AND category = ?2",
+        params,
+    )?;
+    conn.execute(
+        "DELETE FROM checkpoint WHERE game = ?1 AND category = ?2",
+        params,
+    )?;
+    Ok(())
+}
+
+/// A checkpoint as it comes out of the database.
+struct RawCheckpoint {
+    attempt: i64,
+    started: chrono::DateTime<chrono::Utc>,
+    cursor: i64,
+    editor_time: Option<u32>,
+    editor_field: Option<String>,
+    editor_digits: Option<String>,
+}
+
+impl RawCheckpoint {
+    /// Converts this raw checkpoint, its split times `times`, and the IDs of
+    /// its skipped splits `skipped`, into a [Checkpoint].
+    fn cook(self, times: HashMap<String, Vec<Time>>, skipped: Vec<String>) -> Result<Checkpoint> {
+        let field = match self.editor_field {
+            Some(pos) => Some(checkpoint::Field {
+                position: pos.parse()?,
+                digits: self.editor_digits.unwrap_or_default(),
+            }),
+            None => None,
+        };
+        let editor = match self.editor_time {
+            Some(time) => Some(checkpoint::Editor {
+                time: Time::try_from(time)?,
+                field,
+            }),
+            None => None,
+        };
+        Ok(Checkpoint {
+            attempt: usize::try_from(self.attempt).unwrap_or_default(),
+            started: self.started,
+            times,
+            skipped,
+            cursor: usize::try_from(self.cursor).unwrap_or_default(),
+            editor,
+        })
+    }
+}
+
+impl session::Observer for Db {
+    fn on_checkpoint(&mut self, run: &Run, checkpoint: &Checkpoint) {
+        if let Err(e) = self.save_checkpoint(&run.metadata, checkpoint) {
+            eprintln!("couldn't save checkpoint: {e}");
+        }
+    }
+
+    fn on_run_end(&mut self, run: &Run, outcome: Outcome) {
+        if let Err(e) = self.add_run(run, outcome) {
+            eprintln!("couldn't save run: {e}");
+        }
+        // The run is over, so there's nothing left to recover.
+        if let Err(e) = clear_checkpoint(&self.conn, &run.metadata) {
+            eprintln!("couldn't clear checkpoint: {e}");
+        }
+    }
+
+    fn on_practice_end(&mut self, attempt: &Attempt) {
+        if let Err(e) = self.add_practice(attempt) {
+            eprintln!("couldn't save practice attempt: {e}");
+        }
+    }
+
+    fn on_session_end(&mut self, session: &Session) {
+        // Observers can't fail, so the best we can do is report the problem.
+        if let Err(e) = self.add_session(session) {
+            eprintln!("couldn't save session: {e}");
+        }
+    }
+}
+
+/// Converts `x` to i64 for storage, saturating if overly long.
+fn sat_i64(x: usize) -> i64 {
+    i64::try_from(x).unwrap_or(i64::MAX)
+}
+
+/// Enumeration of errors occurring when using the database.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("SQLite error")]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error("malformed time in database")]
+    Time(#[from] time::Error),
+
+    /// We tried to store a run that hadn't started or ended.
+    #[error("tried to store an unfinished run")]
+    RunNotFinished,
+}
+
+/// Shorthand for results over [Error].
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,75 @@
+-- zombiesplit database schema.
+--
+-- Every statement here must be idempotent, as the schema is applied each time
+-- the database is opened.
+
+CREATE TABLE IF NOT EXISTS session
+    ( sessionid   INTEGER PRIMARY KEY
+    , game        TEXT    NOT NULL -- game ID
+    , category    TEXT    NOT NULL -- category ID
+    , start       TEXT    NOT NULL -- RFC 3339 timestamp
+    , end         TEXT             -- RFC 3339 timestamp, NULL if unfinished
+    , attempts    INTEGER NOT NULL
+    , completions INTEGER NOT NULL
+    , best        INTEGER          -- milliseconds, NULL if no completions
+    );
+
+CREATE TABLE IF NOT EXISTS run
+    ( runid    INTEGER PRIMARY KEY
+    , game     TEXT    NOT NULL -- game ID
+    , category TEXT    NOT NULL -- category ID
+    , attempt  INTEGER NOT NULL
+    , start    TEXT    NOT NULL -- RFC 3339 timestamp
+    , end      TEXT    NOT NULL -- RFC 3339 timestamp
+    , reset_at INTEGER          -- split index, NULL if the run was completed
+    , total    INTEGER NOT NULL -- milliseconds
+    );
+
+CREATE INDEX IF NOT EXISTS run_by_category ON run (game, category);
+
+-- The time of each split in each run, for splits that were timed rather than
+-- skipped.
+CREATE TABLE IF NOT EXISTS run_split
+    ( runid INTEGER NOT NULL REFERENCES run (runid)
+    , split TEXT    NOT NULL -- split ID
+    , time  INTEGER NOT NULL -- milliseconds
+    , PRIMARY KEY (runid, split)
+    );
+
+-- Checkpoints of in-progress runs, at most one per game and category.
+CREATE TABLE IF NOT EXISTS checkpoint
+    ( game          TEXT    NOT NULL -- game ID
+    , category      TEXT    NOT NULL -- category ID
+    , attempt       INTEGER NOT NULL
+    , start         TEXT    NOT NULL -- RFC 3339 timestamp
+    , cursor        INTEGER NOT NULL -- split index
+    , editor_time   INTEGER          -- milliseconds, NULL if no editor open
+    , editor_field  TEXT             -- position name, NULL if no field open
+    , editor_digits TEXT             -- NULL if no field open
+    , PRIMARY KEY (game, category)
+    );
+
+CREATE TABLE IF NOT EXISTS checkpoint_time
+    ( game     TEXT    NOT NULL -- game ID
+    , category TEXT    NOT NULL -- category ID
+    , split    TEXT    NOT NULL -- split ID
+    , time     INTEGER NOT NULL -- milliseconds
+    );
+
+CREATE TABLE IF NOT EXISTS checkpoint_skip
+    ( game     TEXT    NOT NULL -- game ID
+    , category TEXT    NOT NULL -- category ID
+    , split    TEXT    NOT NULL -- split ID
+    );
+
+-- Practice attempts at single splits and groups, keyed by IL category.
+CREATE TABLE IF NOT EXISTS practice
+    ( practiceid INTEGER PRIMARY KEY
+    , game       TEXT    NOT NULL -- game ID
+    , category   TEXT    NOT NULL -- IL category ID
+    , start      TEXT    NOT NULL -- RFC 3339 timestamp
+    , end        TEXT    NOT NULL -- RFC 3339 timestamp
+    , total      INTEGER          -- milliseconds, NULL if not completed
+    );
+
+CREATE INDEX IF NOT EXISTS practice_by_category ON practice (game, category);
@@ -2,6 +2,7 @@
 #![warn(clippy::all, clippy::pedantic)]
 
 pub mod config;
+pub mod db;
 pub mod model;
 pub mod presenter;
 pub mod view;
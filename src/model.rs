@@ -1,5 +1,9 @@
 //! Models used in zombiesplit.
+pub mod checkpoint;
+pub mod group;
 pub mod pace;
+pub mod practice;
 pub mod run;
+pub mod session;
 pub mod split;
 pub mod time;
@@ -0,0 +1,122 @@
+//! Checkpoints of in-progress runs, used to recover from crashes.
+
+use super::{
+    run::Run,
+    split::Status,
+    time::{position, Time},
+};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// A snapshot of an in-progress run, along with enough of the state of the
+/// user interface to resume it where it left off.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Checkpoint {
+    /// The attempt number of the run.
+    pub attempt: usize,
+    /// When the run started.
+    pub started: DateTime<Utc>,
+    /// The times entered for each split in the run that has any, keyed by
+    /// split ID.
+    pub times: HashMap<String, Vec<Time>>,
+    /// The IDs of the splits that were skipped.
+    pub skipped: Vec<String>,
+    /// The position of the cursor.
+    pub cursor: usize,
+    /// The state of any open editor.
+    pub editor: Option<Editor>,
+}
+
+impl Checkpoint {
+    /// Takes a checkpoint of `run`, if it has started, with the given cursor
+    /// position and editor state.
+    #[must_use]
+    pub fn new(run: &Run, cursor: usize, editor: Option<Editor>) -> Option<Self> {
+        Some(Self {
+            attempt: run.attempt,
+            started: run.started?,
+            times: run
+                .splits
+                .iter()
+                .filter(|s| s.has_times())
+                .map(|s| (s.id.clone(), s.times().to_vec()))
+                .collect(),
+            skipped: run
+                .splits
+                .iter()
+                .filter(|s| s.status() == Status::Skipped)
+                .map(|s| s.id.clone())
+                .collect(),
+            cursor,
+            editor,
+        })
+    }
+
+    /// Restores the run part of this checkpoint onto `run`.
+    ///
+    /// Any times already in `run` are discarded.  Splits are matched by ID,
+    /// so times (and skips) for splits no longer in `run` are dropped.
+    pub fn restore_onto(&self, run: &mut Run) {
+        run.attempt = self.attempt;
+        run.start(self.started);
+        for split in &mut run.splits {
+            split.clear();
+            for time in self.times.get(&split.id).into_iter().flatten() {
+                split.push(*time);
+            }
+            if self.skipped.contains(&split.id) {
+                split.set_skipped(true);
+            }
+        }
+    }
+}
+
+/// The checkpointed state of a split editor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Editor {
+    /// The time being edited.
+    pub time: Time,
+    /// The state of any open field editor.
+    pub field: Option<Field>,
+}
+
+/// The checkpointed state of a field editor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Field {
+    /// The position being edited.
+    pub position: position::Name,
+    /// The digits entered so far.
+    pub digits: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::split::Split;
+    use std::convert::TryFrom;
+
+    /// Tests that restoring a checkpoint matches splits by ID, dropping times
+    /// for splits that have since gone.
+    #[test]
+    fn restore_by_id() {
+        let time = |ms| Time::try_from(ms).unwrap();
+        let mut run = Run {
+            splits: vec![Split::new("a"), Split::new("b"), Split::new("c")],
+            ..Run::default()
+        };
+        run.start(Utc::now());
+        run.push_to(0, time(1_000));
+        run.push_to(1, time(2_000));
+        run.toggle_skip_at(2);
+        let cp = Checkpoint::new(&run, 2, None).unwrap();
+
+        let mut run = Run {
+            splits: vec![Split::new("c"), Split::new("d"), Split::new("a")],
+            ..Run::default()
+        };
+        cp.restore_onto(&mut run);
+        assert_eq!(run.splits[0].status(), Status::Skipped);
+        assert_eq!(run.splits[1].status(), Status::NotReached);
+        assert_eq!(run.splits[2].times(), &[time(1_000)]);
+    }
+}
@@ -0,0 +1,35 @@
+//! Split groups and related items.
+
+use std::ops::Range;
+
+/// A group of consecutive splits in a run (for instance, the acts of a zone).
+///
+/// Groups don't own their splits; instead, they refer to a span of the run's
+/// split list, so that splits can still be addressed by a flat index.
+#[derive(Clone, Debug)]
+pub struct Group {
+    /// The ID of the group, used to key it in storage.
+    pub id: String,
+    /// The name of the group.
+    pub name: String,
+    /// The index of the first split in the group.
+    pub start: usize,
+    /// The number of splits in the group.
+    pub len: usize,
+    /// How deeply nested this group is inside other groups.
+    pub depth: usize,
+}
+
+impl Group {
+    /// Gets the range of split indices covered by this group.
+    #[must_use]
+    pub fn range(&self) -> Range<usize> {
+        self.start..(self.start + self.len)
+    }
+
+    /// Gets whether this group contains the split at index `split`.
+    #[must_use]
+    pub fn contains(&self, split: usize) -> bool {
+        self.range().contains(&split)
+    }
+}
@@ -1,5 +1,8 @@
 //! Structs and functions for pace computation.
 
+use super::time::Time;
+use std::convert::TryFrom;
+
 /// Possible paces for a split or run.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Pace {
@@ -26,7 +29,7 @@ pub struct PacedTime {
     /// The pace.
     pub pace: Pace,
     /// The time to which `pace` applies.
-    pub time: super::time::Time,
+    pub time: Time,
 }
 
 /// A pair of split pace and run-so-far pace.
@@ -37,3 +40,34 @@ pub struct Pair {
     /// The run-so-far pace.
     pub run_so_far: PacedTime,
 }
+
+/// A difference between a time and the time it is being compared against.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Delta {
+    /// Whether the time was no slower than its comparison.
+    pub ahead: bool,
+    /// The size of the difference.
+    pub time: Time,
+}
+
+impl Delta {
+    /// Works out the difference between `time` and `comparison`.
+    #[must_use]
+    pub fn between(time: Time, comparison: Time) -> Self {
+        let (time, comparison) = (u32::from(time), u32::from(comparison));
+        Self {
+            ahead: time <= comparison,
+            time: Time::try_from(time.abs_diff(comparison)).unwrap_or_default(),
+        }
+    }
+
+    /// Gets the pace this delta represents.
+    #[must_use]
+    pub fn pace(self) -> Pace {
+        if self.ahead {
+            Pace::Ahead
+        } else {
+            Pace::Behind
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Models relating to practising single splits and groups, outside of full
+//! runs.
+//!
+//! Practice happens in individual-level (IL) runs, whose categories are
+//! derived from the IDs of the split or group being practised.
+
+use super::{run::Run, split::Split, time::Time};
+use chrono::{DateTime, Utc};
+use std::fmt::{self, Display};
+
+/// The prefix of IL category IDs.
+pub const IL_PREFIX: &str = "il";
+
+/// Gets the ID of the IL category for the split or group with ID `id`.
+#[must_use]
+pub fn il_category_id(id: &str) -> String {
+    format!("{IL_PREFIX}/{id}")
+}
+
+/// Gets the display name of the IL category for the split or group named
+/// `name`.
+#[must_use]
+pub fn il_category_name(name: &str) -> String {
+    format!("{name} (IL)")
+}
+
+/// Something in a run that can be practised on its own.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Target {
+    /// The split with the given index.
+    Split(usize),
+    /// The group with the given index.
+    Group(usize),
+}
+
+/// A finished practice attempt.
+#[derive(Clone, Debug)]
+pub struct Attempt {
+    /// The ID of the game.
+    pub game_id: String,
+    /// The ID of the IL category practised.
+    pub category_id: String,
+    /// When the attempt started.
+    pub started: DateTime<Utc>,
+    /// When the attempt ended.
+    pub ended: DateTime<Utc>,
+    /// The total time, if every split in the attempt was timed.
+    pub total: Option<Time>,
+}
+
+impl Attempt {
+    /// Makes an attempt out of the finished IL run `run`.
+    ///
+    /// Returns `None` if the run hasn't both started and ended, or if nothing
+    /// in it was timed.
+    #[must_use]
+    pub fn from_run(run: &Run) -> Option<Self> {
+        if !run.splits.iter().any(Split::has_times) {
+            return None;
+        }
+        Some(Self {
+            game_id: run.metadata.game_id.clone(),
+            category_id: run.metadata.category_id.clone(),
+            started: run.started?,
+            ended: run.ended?,
+            total: Some(run.total()).filter(|_| run.is_completed()),
+        })
+    }
+}
+
+/// Statistics about practice of one split or group.
+#[derive(Clone, Debug, Default)]
+pub struct Stats {
+    /// Number of practice attempts.
+    pub attempts: usize,
+    /// Number of practice attempts in which every split was timed.
+    pub completions: usize,
+    /// The best completed time, if any.
+    pub best: Option<Time>,
+}
+
+impl Stats {
+    /// Adds `attempt` to these statistics.
+    pub fn add(&mut self, attempt: &Attempt) {
+        self.attempts += 1;
+        if let Some(total) = attempt.total {
+            self.completions += 1;
+            self.best = Some(self.best.map_or(total, |best| best.min(total)));
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} attempt(s), {} completed",
+            self.attempts, self.completions
+        )?;
+        if let Some(best) = self.best {
+            write!(f, ", best {best}")?;
+        }
+        Ok(())
+    }
+}
@@ -1,27 +1,102 @@
 //! Models relating to an in-progress run.
 
 use super::{
+    group::Group,
     pace,
-    split::{Comparison, Split},
+    practice::{self, Target},
+    split::{Against, Comparison, Split, Status},
     time::Time,
 };
+use chrono::{DateTime, Utc};
+use std::convert::TryFrom;
 
 /// An in-progress run.
+///
+/// The default run has no splits, and stands in until a real run is loaded.
+#[derive(Default)]
 pub struct Run {
     pub metadata: Metadata,
     /// The attempt number of this run.
     pub attempt: usize,
+    /// When this run started, if it has.
+    pub started: Option<DateTime<Utc>>,
+    /// When this run ended, if it has.
+    pub ended: Option<DateTime<Utc>>,
     pub splits: Vec<Split>,
+    /// The groups into which the splits are arranged, in split order.
+    pub groups: Vec<Group>,
     pub comparisons: Vec<Comparison>,
+    /// What this run's splits are compared against.
+    pub against: Against,
+    /// The best completed time for this run's category, if known.
+    pub pb: Option<Time>,
 }
 
 impl Run {
-    /// Wipes all times for this run.
+    /// Wipes all times for this run, and moves onto the next attempt.
+    ///
+    /// The times are first folded into the comparisons, so that they stay
+    /// in line with the run history.
     pub fn reset(&mut self) {
+        self.fold_into_comparisons();
         self.attempt += 1;
+        self.started = None;
+        self.ended = None;
         self.splits.iter_mut().for_each(Split::clear)
     }
 
+    /// Marks this run as having started at `time`.
+    pub fn start(&mut self, time: DateTime<Utc>) {
+        self.started = Some(time);
+        self.ended = None;
+    }
+
+    /// Marks this run as having ended at `time`, and works out its outcome.
+    pub fn end(&mut self, time: DateTime<Utc>) -> Outcome {
+        self.ended = Some(time);
+        self.outcome()
+    }
+
+    /// Gets whether this run has started, but not yet ended.
+    #[must_use]
+    pub fn is_in_progress(&self) -> bool {
+        self.started.is_some() && self.ended.is_none()
+    }
+
+    /// Gets the outcome of this run, were it to end now.
+    ///
+    /// Skipped splits don't stop a run from being completed.
+    #[must_use]
+    pub fn outcome(&self) -> Outcome {
+        self.splits
+            .iter()
+            .position(|s| !s.is_passed())
+            .map_or(Outcome::Completed, Outcome::Reset)
+    }
+
+    /// Folds the times of this run into its comparisons.
+    ///
+    /// Timed splits that beat their best times become the new best times,
+    /// and if the run was completed as a personal best, its times become the
+    /// personal best run's.
+    fn fold_into_comparisons(&mut self) {
+        for (s, c) in self.splits.iter().zip(self.comparisons.iter_mut()) {
+            if s.status() == Status::Completed {
+                let time = s.summed_time();
+                c.split = Some(c.split.map_or(time, |best| best.min(time)));
+            }
+        }
+
+        let total = self.total();
+        if !self.is_completed() || self.pb.is_some_and(|pb| pb < total) {
+            return;
+        }
+        self.pb = Some(total);
+        for (s, c) in self.splits.iter().zip(self.comparisons.iter_mut()) {
+            c.in_run = Some(s.summed_time()).filter(|_| s.status() == Status::Completed);
+        }
+    }
+
     /// Pushes the time `time` onto the split at `split`, if it exists.
     pub fn push_to(&mut self, split: usize, time: Time) {
         if let Some(ref mut s) = self.splits.get_mut(split) {
@@ -34,13 +109,38 @@ impl Run {
         self.splits.get_mut(split).and_then(Split::pop)
     }
 
-    /// Removes all times from the split at `split`, if it exists.
+    /// Removes all times from the split at `split`, and un-skips it, if it
+    /// exists.
     pub fn reset_at(&mut self, split: usize) {
         if let Some(s) = self.splits.get_mut(split) {
             s.clear()
         }
     }
 
+    /// Skips the split at `split` if it isn't already skipped, and un-skips
+    /// it otherwise, returning whether it is now skipped.
+    pub fn toggle_skip_at(&mut self, split: usize) -> bool {
+        self.splits.get_mut(split).is_some_and(|s| {
+            let skipped = s.status() != Status::Skipped;
+            s.set_skipped(skipped);
+            skipped
+        })
+    }
+
+    /// Gets whether every split in this run has a time or was skipped.
+    #[must_use]
+    pub fn is_completed(&self) -> bool {
+        !self.splits.is_empty() && self.splits.iter().all(Split::is_passed)
+    }
+
+    /// Gets the total time across all splits in this run.
+    ///
+    /// This includes any time spent on skipped splits.
+    #[must_use]
+    pub fn total(&self) -> Time {
+        self.splits.iter().map(Split::summed_time).sum()
+    }
+
     /// Gets the total time up to and including `split`.
     #[must_use]
     pub fn total_at(&self, split: usize) -> Time {
@@ -51,6 +151,81 @@ impl Run {
             .sum()
     }
 
+    /// Gets the number of splits in this run that are personal bests against
+    /// their comparisons ('gold splits').
+    #[must_use]
+    pub fn golds(&self) -> usize {
+        (0..self.splits.len()).filter(|i| self.is_gold(*i)).count()
+    }
+
+    /// Gets whether the split at `split` is a personal best against its
+    /// comparison.
+    #[must_use]
+    pub fn is_gold(&self, split: usize) -> bool {
+        self.splits.get(split).is_some_and(Split::has_times)
+            && self.paced_time_at(split).split.pace == pace::Pace::PersonalBest
+    }
+
+    /// Gets the difference between the time of the split at `split` and its
+    /// comparison time, if both exist and the split wasn't skipped.
+    #[must_use]
+    pub fn segment_delta(&self, split: usize) -> Option<pace::Delta> {
+        let s = self.splits.get(split)?;
+        if s.status() != Status::Completed {
+            return None;
+        }
+        let cmp = self.comparison_time(split)?;
+        Some(pace::Delta::between(s.summed_time(), cmp))
+    }
+
+    /// Projects the final time of this run: the times of the splits the run
+    /// has passed, plus the comparison times of the ones it hasn't.
+    ///
+    /// This is `None` if any split not yet reached has no comparison time.
+    #[must_use]
+    pub fn projected_total(&self) -> Option<Time> {
+        self.splits
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                if s.is_passed() {
+                    Some(s.summed_time())
+                } else {
+                    self.comparison_time(i)
+                }
+            })
+            .sum()
+    }
+
+    /// Gets how much time could be saved on the split at `split`: the
+    /// difference between its comparison time and its best time.
+    #[must_use]
+    pub fn possible_time_save(&self, split: usize) -> Option<Time> {
+        let cmp = u32::from(self.comparison_time(split)?);
+        let best = u32::from(self.comparisons.get(split)?.split?);
+        Time::try_from(cmp.saturating_sub(best)).ok()
+    }
+
+    /// Gets the time the split at `split` is compared against, if any.
+    #[must_use]
+    pub fn comparison_time(&self, split: usize) -> Option<Time> {
+        self.comparisons.get(split)?.time(self.against)
+    }
+
+    /// Gets the sum of the best times of every split in this run, if every
+    /// split has a best time.
+    #[must_use]
+    pub fn sum_of_best(&self) -> Option<Time> {
+        if self.comparisons.len() < self.splits.len() {
+            return None;
+        }
+        self.comparisons
+            .iter()
+            .take(self.splits.len())
+            .map(|c| c.split)
+            .sum()
+    }
+
     /// Gets the paced time for the split at `split`.
     /// Said pace is made up of the split and run-so-far paces.
     #[must_use]
@@ -61,6 +236,167 @@ impl Run {
         }
     }
 
+    /// Finds the split named `name`, ignoring case; failing that, finds the
+    /// first split whose name starts with `name`.
+    #[must_use]
+    pub fn find_split(&self, name: &str) -> Option<usize> {
+        let name = name.to_lowercase();
+        let names: Vec<String> = self.splits.iter().map(|s| s.name.to_lowercase()).collect();
+        names
+            .iter()
+            .position(|n| *n == name)
+            .or_else(|| names.iter().position(|n| n.starts_with(&name)))
+    }
+
+    /// Gets the indices of the first splits of each top-level group.
+    #[must_use]
+    pub fn group_starts(&self) -> Vec<usize> {
+        self.groups
+            .iter()
+            .filter(|g| g.depth == 0)
+            .map(|g| g.start)
+            .collect()
+    }
+
+    /// Gets the index of the outermost group containing the split at `split`,
+    /// if any.
+    #[must_use]
+    pub fn group_of(&self, split: usize) -> Option<usize> {
+        self.groups.iter().position(|g| g.contains(split))
+    }
+
+    /// Gets the index of the innermost group containing the split at `split`,
+    /// if any.
+    #[must_use]
+    pub fn innermost_group_of(&self, split: usize) -> Option<usize> {
+        // Groups come in split order, so inner groups follow their parents.
+        self.groups.iter().rposition(|g| g.contains(split))
+    }
+
+    /// Gets how deeply nested the split at `split` is inside groups, not
+    /// counting its outermost group.
+    #[must_use]
+    pub fn depth_of(&self, split: usize) -> usize {
+        self.groups
+            .iter()
+            .filter(|g| g.contains(split))
+            .count()
+            .saturating_sub(1)
+    }
+
+    /// Carves out a fresh individual-level run for practising `target` on its
+    /// own, or `None` if `target` doesn't exist.
+    ///
+    /// The IL run has untimed copies of the target's splits, along with the
+    /// target itself (if a group) and any groups nested inside it, and the
+    /// comparisons of those splits.
+    #[must_use]
+    pub fn il(&self, target: Target) -> Option<Run> {
+        let (id, name, range, depth) = match target {
+            Target::Split(i) => {
+                let s = self.splits.get(i)?;
+                (&s.id, &s.name, i..i + 1, None)
+            }
+            Target::Group(g) => {
+                let g = self.groups.get(g)?;
+                (&g.id, &g.name, g.range(), Some(g.depth))
+            }
+        };
+
+        let mut splits = self.splits.get(range.clone())?.to_vec();
+        splits.iter_mut().for_each(Split::clear);
+        let groups = self
+            .groups
+            .iter()
+            .filter(|g| {
+                depth.is_some_and(|d| d <= g.depth)
+                    && range.start <= g.start
+                    && g.range().end <= range.end
+            })
+            .map(|g| Group {
+                start: g.start - range.start,
+                depth: g.depth - depth.unwrap_or_default(),
+                ..g.clone()
+            })
+            .collect();
+
+        Some(Run {
+            metadata: Metadata {
+                game_id: self.metadata.game_id.clone(),
+                category_id: practice::il_category_id(id),
+                game: self.metadata.game.clone(),
+                category: practice::il_category_name(name),
+            },
+            attempt: 0,
+            started: None,
+            ended: None,
+            comparisons: self
+                .comparisons
+                .get(range.clone())
+                .map(<[Comparison]>::to_vec)
+                .unwrap_or_default(),
+            splits,
+            groups,
+            against: self.against,
+            pb: None,
+        })
+    }
+
+    /// Takes on any best times from `il`, an IL run carved out of this run
+    /// starting at the split at `start`.
+    ///
+    /// The best times come from both the comparisons of `il` and its
+    /// current times, so that practice can set best segments.
+    pub fn adopt_bests(&mut self, il: &Run, start: usize) {
+        let targets = self.comparisons.iter_mut().skip(start);
+        for ((s, c), target) in il.splits.iter().zip(&il.comparisons).zip(targets) {
+            let time = Some(s.summed_time()).filter(|_| s.status() == Status::Completed);
+            target.split = [target.split, c.split, time]
+                .iter()
+                .flatten()
+                .min()
+                .copied();
+        }
+    }
+
+    /// Gets the paced subtotal for the group at `group`.
+    ///
+    /// The group is ahead of its comparison if its subtotal is no more than
+    /// the sum of the comparison times of the splits it has passed so far;
+    /// if it hasn't passed any, or any of those splits have no comparison
+    /// time, the pace is inconclusive.
+    #[must_use]
+    pub fn group_paced_time_at(&self, group: usize) -> pace::PacedTime {
+        self.groups
+            .get(group)
+            .map_or(pace::PacedTime::default(), |g| {
+                let splits = self.splits.get(g.range()).unwrap_or(&[]);
+                let time = splits.iter().map(Split::summed_time).sum();
+                pace::PacedTime {
+                    pace: self.group_pace(g, time),
+                    time,
+                }
+            })
+    }
+
+    fn group_pace(&self, group: &Group, time: Time) -> pace::Pace {
+        let passed: Vec<usize> = group
+            .range()
+            .filter(|i| self.splits.get(*i).is_some_and(Split::is_passed))
+            .collect();
+        if passed.is_empty() {
+            return pace::Pace::default();
+        }
+        let cmp: Option<Time> = passed.into_iter().map(|i| self.comparison_time(i)).sum();
+        cmp.map_or(pace::Pace::default(), |cmp| {
+            if time <= cmp {
+                pace::Pace::Ahead
+            } else {
+                pace::Pace::Behind
+            }
+        })
+    }
+
     fn run_paced_time_at(&self, split: usize) -> pace::PacedTime {
         let time = self.total_at(split);
         // TODO(@MattWindsor91): do this.
@@ -81,17 +417,210 @@ impl Run {
             })
     }
 
+    /// Gets the pace of the split at `split` against its comparison.
+    ///
+    /// Skipped splits aren't compared, so they can't be ahead, behind, or
+    /// bests.
     fn split_pace_at(&self, split: usize, time: Time) -> pace::Pace {
+        if self.splits.get(split).map(Split::status) == Some(Status::Skipped) {
+            return pace::Pace::default();
+        }
         self.comparisons
             .get(split)
-            .map_or(pace::Pace::default(), |c| c.pace(time))
+            .map_or(pace::Pace::default(), |c| c.pace(time, self.against))
     }
 }
 
+/// How a run ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    /// Every split in the run has a time.
+    Completed,
+    /// The run was reset before the split with the given index had a time.
+    Reset(usize),
+}
+
 /// Metadata in a run.
+#[derive(Default)]
 pub struct Metadata {
+    /// The ID of the game, used to key it in storage.
+    pub game_id: String,
+    /// The ID of the category, used to key it in storage.
+    pub category_id: String,
     /// The name of the game.
     pub game: String,
     /// The name of the category.
     pub category: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Shorthand for making a time of `ms` milliseconds.
+    fn time(ms: u32) -> Time {
+        Time::try_from(ms).unwrap()
+    }
+
+    /// Builds a run with one split for each name, best segment time, and
+    /// PB run time in `splits`.
+    fn run_with(splits: &[(&str, Option<u32>, Option<u32>)]) -> Run {
+        Run {
+            splits: splits.iter().map(|(name, _, _)| Split::new(name)).collect(),
+            comparisons: splits
+                .iter()
+                .map(|&(_, split, in_run)| Comparison {
+                    split: split.map(time),
+                    in_run: in_run.map(time),
+                })
+                .collect(),
+            ..Run::default()
+        }
+    }
+
+    /// Tests that skipped splits count towards completion and totals, but
+    /// can't be personal bests.
+    #[test]
+    fn skipped_splits() {
+        let mut run = run_with(&[
+            ("a", Some(10_000), None),
+            ("b", Some(10_000), None),
+            ("c", Some(10_000), None),
+        ]);
+        run.push_to(0, time(1_000));
+        run.push_to(1, time(2_000));
+        assert_eq!(run.outcome(), Outcome::Reset(2));
+
+        assert!(run.toggle_skip_at(1));
+        assert!(run.toggle_skip_at(2));
+        assert_eq!(run.outcome(), Outcome::Completed);
+        assert!(run.is_completed());
+        assert_eq!(run.total(), time(3_000));
+        assert_eq!(run.paced_time_at(0).split.pace, pace::Pace::PersonalBest);
+        assert_eq!(run.paced_time_at(1).split.pace, pace::Pace::Inconclusive);
+
+        assert!(!run.toggle_skip_at(2));
+        run.reset_at(1);
+        assert_eq!(run.splits[1].status(), Status::NotReached);
+    }
+
+    /// Tests projecting the final time, and working out time saves and the
+    /// sum of best, from comparisons.
+    #[test]
+    fn projections() {
+        let mut run = run_with(&[
+            ("a", Some(900), Some(1_000)),
+            ("b", Some(1_500), Some(2_000)),
+        ]);
+        assert_eq!(run.projected_total(), Some(time(3_000)));
+        run.push_to(0, time(1_200));
+        assert_eq!(run.projected_total(), Some(time(3_200)));
+        assert_eq!(
+            run.segment_delta(0),
+            Some(pace::Delta {
+                ahead: false,
+                time: time(200)
+            })
+        );
+        assert_eq!(run.segment_delta(1), None);
+        assert_eq!(run.possible_time_save(1), Some(time(500)));
+        assert_eq!(run.sum_of_best(), Some(time(2_400)));
+
+        run.against = Against::BestSegments;
+        assert_eq!(run.projected_total(), Some(time(2_700)));
+        assert_eq!(run.possible_time_save(1), Some(time(0)));
+        run.against = Against::Nothing;
+        assert_eq!(run.segment_delta(0), None);
+        run.against = Against::PersonalBest;
+
+        run.comparisons[1].in_run = None;
+        assert_eq!(run.projected_total(), None);
+        run.comparisons.pop();
+        assert_eq!(run.sum_of_best(), None);
+    }
+
+    /// Tests that a group in progress is only paced against the splits it
+    /// has passed.
+    #[test]
+    fn group_pace() {
+        let mut run = Run {
+            groups: vec![Group {
+                id: "g".to_owned(),
+                name: "G".to_owned(),
+                start: 0,
+                len: 2,
+                depth: 0,
+            }],
+            ..run_with(&[("a", None, Some(1_000)), ("b", None, Some(1_000))])
+        };
+        assert_eq!(run.group_paced_time_at(0).pace, pace::Pace::Inconclusive);
+        run.push_to(0, time(1_500));
+        assert_eq!(run.group_paced_time_at(0).pace, pace::Pace::Behind);
+        run.push_to(1, time(400));
+        assert_eq!(run.group_paced_time_at(0).pace, pace::Pace::Ahead);
+    }
+
+    /// Tests that resetting a run folds its times into the comparisons.
+    #[test]
+    fn fold_into_comparisons() {
+        let mut run = Run {
+            pb: Some(time(3_000)),
+            ..run_with(&[
+                ("a", Some(900), Some(1_000)),
+                ("b", Some(1_500), Some(2_000)),
+            ])
+        };
+        run.push_to(0, time(800));
+        run.reset();
+        assert_eq!(run.comparisons[0].split, Some(time(800)));
+        assert_eq!(run.comparisons[0].in_run, Some(time(1_000)));
+        assert_eq!(run.pb, Some(time(3_000)));
+
+        run.push_to(0, time(1_000));
+        run.push_to(1, time(1_900));
+        run.reset();
+        assert_eq!(run.comparisons[0].split, Some(time(800)));
+        assert_eq!(run.comparisons[1].in_run, Some(time(1_900)));
+        assert_eq!(run.pb, Some(time(2_900)));
+    }
+
+    /// Tests that golds come from beating best segments, and that skipped
+    /// splits neither count as golds nor become best segments.
+    #[test]
+    fn golds() {
+        let mut run = run_with(&[
+            ("a", Some(1_000), Some(1_200)),
+            ("b", Some(1_000), Some(1_200)),
+            ("c", Some(1_000), Some(1_200)),
+        ]);
+        run.push_to(0, time(900));
+        run.push_to(1, time(1_100));
+        run.push_to(2, time(500));
+        run.toggle_skip_at(2);
+        assert!(run.is_gold(0));
+        assert!(!run.is_gold(1));
+        assert!(!run.is_gold(2));
+        assert_eq!(run.golds(), 1);
+
+        run.reset();
+        assert_eq!(run.comparisons[0].split, Some(time(900)));
+        assert_eq!(run.comparisons[2].split, Some(time(1_000)));
+        assert_eq!(run.golds(), 0);
+    }
+
+    /// Tests that IL runs carry their splits' comparisons, and that best
+    /// times set in them carry back over to the full run.
+    #[test]
+    fn practice_bests() {
+        let mut run = run_with(&[("a", Some(1_000), None), ("b", Some(1_000), None)]);
+        let mut il = run.il(Target::Split(1)).unwrap();
+        assert_eq!(il.comparisons.len(), 1);
+        il.push_to(0, time(1_100));
+        il.reset();
+        il.push_to(0, time(900));
+
+        run.adopt_bests(&il, 1);
+        assert_eq!(run.comparisons[0].split, Some(time(1_000)));
+        assert_eq!(run.comparisons[1].split, Some(time(900)));
+    }
+}
@@ -0,0 +1,165 @@
+//! Models relating to a session of runs.
+
+use super::{
+    checkpoint::Checkpoint,
+    practice,
+    run::{Metadata, Outcome, Run},
+    time::Time,
+};
+use chrono::{DateTime, Utc};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::{self, Display},
+    rc::Rc,
+};
+
+/// A session: every attempt at one game and category made between starting
+/// zombiesplit (or switching to that category) and quitting (or switching
+/// away from it).
+#[derive(Clone, Debug)]
+pub struct Session {
+    /// The ID of the game attempted in this session.
+    pub game_id: String,
+    /// The ID of the category attempted in this session.
+    pub category_id: String,
+    /// When the session started.
+    pub start: DateTime<Utc>,
+    /// When the session ended, if it has.
+    pub end: Option<DateTime<Utc>>,
+    /// Number of attempts started in this session.
+    pub attempts: usize,
+    /// Number of attempts completed in this session.
+    pub completions: usize,
+    /// The best completed time in this session, if any.
+    pub best: Option<Time>,
+    /// Practice statistics for this session, keyed by game ID and IL
+    /// category ID.
+    ///
+    /// Practice attempts don't count towards any of the other statistics.
+    pub practice: HashMap<(String, String), practice::Stats>,
+}
+
+impl Session {
+    /// Creates a new, empty session starting at `start`, for the game and
+    /// category in `meta`.
+    #[must_use]
+    pub fn new(start: DateTime<Utc>, meta: &Metadata) -> Self {
+        Self {
+            game_id: meta.game_id.clone(),
+            category_id: meta.category_id.clone(),
+            start,
+            end: None,
+            attempts: 0,
+            completions: 0,
+            best: None,
+            practice: HashMap::new(),
+        }
+    }
+
+    /// Gets whether this session is for the game and category in `meta`.
+    #[must_use]
+    pub fn is_for(&self, meta: &Metadata) -> bool {
+        self.game_id == meta.game_id && self.category_id == meta.category_id
+    }
+
+    /// Gets whether this session has ended.
+    #[must_use]
+    pub fn has_ended(&self) -> bool {
+        self.end.is_some()
+    }
+
+    /// Registers the start of a new attempt.
+    pub fn begin_attempt(&mut self) {
+        self.attempts += 1;
+    }
+
+    /// Registers the end of the attempt represented by `run`.
+    ///
+    /// If the run was completed, it counts towards the completion count and
+    /// the session best.
+    pub fn end_attempt(&mut self, run: &Run) {
+        if !run.is_completed() {
+            return;
+        }
+        self.completions += 1;
+        let total = run.total();
+        self.best = Some(self.best.map_or(total, |best| best.min(total)));
+    }
+
+    /// Registers the practice attempt `attempt`.
+    pub fn end_practice(&mut self, attempt: &practice::Attempt) {
+        self.practice
+            .entry((attempt.game_id.clone(), attempt.category_id.clone()))
+            .or_default()
+            .add(attempt);
+    }
+
+    /// Gets the practice statistics for the game with ID `game` and IL
+    /// category with ID `category`.
+    #[must_use]
+    pub fn practice_stats(&self, game: &str, category: &str) -> practice::Stats {
+        self.practice
+            .get(&(game.to_owned(), category.to_owned()))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Marks this session as having ended at `end`.
+    pub fn finish(&mut self, end: DateTime<Utc>) {
+        self.end = Some(end);
+    }
+}
+
+impl Display for Session {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}: {} attempt(s), {} completed",
+            self.game_id, self.category_id, self.attempts, self.completions
+        )?;
+        if let Some(best) = self.best {
+            write!(f, ", best {best}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Trait for things that observe a session (for instance, to persist it).
+pub trait Observer {
+    /// Called when `run` has been checkpointed as `checkpoint`.
+    ///
+    /// This happens whenever the run, or the state of the user interface
+    /// around it, may have changed.
+    fn on_checkpoint(&mut self, _run: &Run, _checkpoint: &Checkpoint) {}
+
+    /// Called when `run` has ended with outcome `outcome`.
+    fn on_run_end(&mut self, _run: &Run, _outcome: Outcome) {}
+
+    /// Called when the practice attempt `attempt` has ended.
+    fn on_practice_end(&mut self, _attempt: &practice::Attempt) {}
+
+    /// Called when `session` has ended.
+    fn on_session_end(&mut self, _session: &Session) {}
+}
+
+/// Shared observers observe through their shared reference, so that (for
+/// instance) a database can both observe a session and be read from while
+/// it runs.
+impl<T: Observer + ?Sized> Observer for Rc<RefCell<T>> {
+    fn on_checkpoint(&mut self, run: &Run, checkpoint: &Checkpoint) {
+        self.borrow_mut().on_checkpoint(run, checkpoint);
+    }
+
+    fn on_run_end(&mut self, run: &Run, outcome: Outcome) {
+        self.borrow_mut().on_run_end(run, outcome);
+    }
+
+    fn on_practice_end(&mut self, attempt: &practice::Attempt) {
+        self.borrow_mut().on_practice_end(attempt);
+    }
+
+    fn on_session_end(&mut self, session: &Session) {
+        self.borrow_mut().on_session_end(session);
+    }
+}
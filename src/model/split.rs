@@ -1,32 +1,99 @@
 //! Splits and related items.
 
 use super::{pace::Pace, time::Time};
+use std::path::PathBuf;
 
 /// A split in a run.
+#[derive(Clone)]
 pub struct Split {
+    /// The ID of the split, used to key it in storage.
+    pub id: String,
     /// The name of the split.
     pub name: String,
+    /// A short name for the split, if any.
+    pub short: Option<String>,
+    /// The path to an icon for the split, if any.
+    pub icon: Option<PathBuf>,
     /// The entered times.
     /// Invariant: none of the times are zero.
     times: Vec<Time>,
+    /// Whether the split was deliberately skipped.
+    skipped: bool,
+}
+
+/// How far a run has got with a split.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Status {
+    /// The split has no times, and hasn't been skipped: the run hasn't
+    /// reached it yet.
+    NotReached,
+    /// The split was deliberately skipped.
+    ///
+    /// Any times it has still count towards totals, but not towards bests.
+    Skipped,
+    /// The split has times.
+    Completed,
 }
 
 impl Split {
     /// Creates a new split with the given name and an empty time.
+    ///
+    /// The split's ID starts out as its name.
     #[must_use]
     pub fn new(name: &str) -> Self {
         Self {
+            id: name.to_owned(),
             name: name.to_owned(),
+            short: None,
+            icon: None,
             times: Vec::new(),
+            skipped: false,
         }
     }
 
+    /// Gets the short name of the split if it has one, and the full name
+    /// otherwise.
+    #[must_use]
+    pub fn short_name(&self) -> &str {
+        self.short.as_deref().unwrap_or(&self.name)
+    }
+
     /// Calculates the summed time of the split.
     #[must_use]
     pub fn summed_time(&self) -> Time {
         self.times.iter().copied().sum()
     }
 
+    /// Borrows the times registered for this split.
+    #[must_use]
+    pub fn times(&self) -> &[Time] {
+        &self.times
+    }
+
+    /// Gets the status of this split.
+    #[must_use]
+    pub fn status(&self) -> Status {
+        if self.skipped {
+            Status::Skipped
+        } else if self.has_times() {
+            Status::Completed
+        } else {
+            Status::NotReached
+        }
+    }
+
+    /// Gets whether the run has got past this split, either by completing it
+    /// or by skipping it.
+    #[must_use]
+    pub fn is_passed(&self) -> bool {
+        self.status() != Status::NotReached
+    }
+
+    /// Sets whether this split is skipped.
+    pub fn set_skipped(&mut self, skipped: bool) {
+        self.skipped = skipped;
+    }
+
     /// Gets whether this split has times registered.
     #[must_use]
     pub fn has_times(&self) -> bool {
@@ -48,13 +115,48 @@ impl Split {
         self.times.pop()
     }
 
-    /// Removes all times from this split.
+    /// Removes all times from this split, and un-skips it.
     pub fn clear(&mut self) {
-        self.times.clear()
+        self.times.clear();
+        self.skipped = false;
+    }
+}
+
+/// What a run's splits are compared against.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Against {
+    /// Each split's time in the personal best run.
+    PersonalBest,
+    /// Each split's best time.
+    BestSegments,
+    /// Nothing; splits are still checked for golds, but aren't paced.
+    Nothing,
+}
+
+/// Splits are compared against the personal best run by default.
+impl Default for Against {
+    fn default() -> Self {
+        Self::PersonalBest
+    }
+}
+
+impl Against {
+    /// Every comparison, with its name.
+    pub const ALL: &'static [(Self, &'static str)] = &[
+        (Self::PersonalBest, "pb"),
+        (Self::BestSegments, "best"),
+        (Self::Nothing, "none"),
+    ];
+
+    /// Looks up the comparison named `name`.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().find(|(_, n)| *n == name).map(|(a, _)| *a)
     }
 }
 
 /// Split comparisons.
+#[derive(Clone, Debug)]
 pub struct Comparison {
     /// The personal best for this split, if any.
     pub split: Option<Time>,
@@ -63,18 +165,28 @@ pub struct Comparison {
 }
 
 impl Comparison {
+    /// Gets the time to compare against, if any.
+    #[must_use]
+    pub fn time(&self, against: Against) -> Option<Time> {
+        match against {
+            Against::PersonalBest => self.in_run,
+            Against::BestSegments => self.split,
+            Against::Nothing => None,
+        }
+    }
+
     /// Compares `split_time` against this comparison.
     #[must_use]
-    pub fn pace(&self, split_time: Time) -> Pace {
+    pub fn pace(&self, split_time: Time, against: Against) -> Pace {
         if self.is_pb(split_time) {
             Pace::PersonalBest
         } else {
-            self.pace_from_run(split_time)
+            self.pace_against(split_time, against)
         }
     }
 
-    fn pace_from_run(&self, split_time: Time) -> Pace {
-        self.in_run.map_or(Pace::default(), |cmp| {
+    fn pace_against(&self, split_time: Time, against: Against) -> Pace {
+        self.time(against).map_or(Pace::default(), |cmp| {
             if split_time <= cmp {
                 Pace::Ahead
             } else {
@@ -16,6 +16,8 @@ pub enum Error {
     FieldTooBig { pos: position::Name, val: u32 },
     #[error("millisecond value {0} too large")]
     MsecOverflow(u32),
+    #[error("unknown field position: {0}")]
+    UnknownPosition(String),
 }
 
 /// Shorthand for parse results.
@@ -1,7 +1,9 @@
 //! Position-specific field logic.
+use super::error::Error;
 use std::{
     borrow::Cow,
     fmt::{self, Display, Write},
+    str::FromStr,
 };
 
 const MINS_IN_HOUR: u32 = 60;
@@ -171,7 +173,7 @@ impl Position for Msec {
 }
 
 /// Names of parseable time fields.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Name {
     /// Denotes the hours field.
     Hours,
@@ -192,8 +194,22 @@ impl Display for Name {
                 Self::Hours => "hours",
                 Self::Minutes => "minutes",
                 Self::Seconds => "seconds",
-                Self::Milliseconds => "msececonds",
+                Self::Milliseconds => "milliseconds",
             }
         )
     }
 }
+
+impl FromStr for Name {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "hours" => Ok(Self::Hours),
+            "minutes" => Ok(Self::Minutes),
+            "seconds" => Ok(Self::Seconds),
+            "milliseconds" => Ok(Self::Milliseconds),
+            _ => Err(Error::UnknownPosition(s.to_owned())),
+        }
+    }
+}
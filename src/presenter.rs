@@ -1,12 +1,27 @@
 //! Contains all of the state held by the user interface.
 
+pub mod command;
 pub mod cursor;
 pub mod editor;
 pub mod event;
+pub mod finish;
+pub mod flash;
 pub mod mode;
 pub mod nav;
+pub mod pending;
+pub mod pick;
+pub mod practice;
+pub mod prompt;
+pub mod recover;
+pub mod search;
 
-use crate::model::{pace, run};
+use crate::model::{
+    checkpoint::Checkpoint,
+    pace,
+    practice::{Attempt, Stats, Target},
+    run, session,
+    split::Against,
+};
 pub use editor::Editor;
 
 use self::cursor::SplitPosition;
@@ -18,6 +33,28 @@ pub struct Presenter {
     pub mode: Box<dyn mode::Mode>,
     /// The current run.
     pub run: run::Run,
+    /// The current session.
+    pub session: session::Session,
+    /// Observers to notify about the session.
+    observers: Vec<Box<dyn session::Observer>>,
+    /// If we are practising, the full run set aside while we do so.
+    practice: Option<practice::SetAside>,
+    /// The source of games and categories to pick from, if any.
+    source: Option<Box<dyn pick::Source>>,
+    /// If we are picking a new category mid-session, the mode to return to
+    /// if the user changes their mind.
+    before_pick: Option<Box<dyn mode::Mode>>,
+    /// If we are at the command line or typing a search, the mode to return
+    /// to when we leave.
+    before_line: Option<Box<dyn mode::Mode>>,
+    /// The last search accepted, if any.
+    last_search: Option<String>,
+    /// Any count or prefix typed ahead of a command.
+    pending: pending::Pending,
+    /// The most recent celebration, if any.
+    flash: Option<flash::Flash>,
+    /// The last checkpoint sent to the observers, if it still stands.
+    last_checkpoint: Option<Checkpoint>,
 }
 
 impl Presenter {
@@ -26,10 +63,40 @@ impl Presenter {
     pub fn new(run: run::Run) -> Self {
         Self {
             mode: Box::new(mode::Inactive),
+            session: session::Session::new(chrono::Utc::now(), &run.metadata),
             run,
+            observers: vec![],
+            practice: None,
+            source: None,
+            before_pick: None,
+            before_line: None,
+            last_search: None,
+            pending: pending::Pending::default(),
+            flash: None,
+            last_checkpoint: None,
         }
     }
 
+    /// Sets the source from which the user can pick games and categories.
+    pub fn set_source(&mut self, source: Box<dyn pick::Source>) {
+        self.source = Some(source);
+    }
+
+    /// Adds an observer to be notified about the session.
+    pub fn add_observer(&mut self, observer: Box<dyn session::Observer>) {
+        self.observers.push(observer);
+    }
+
+    /// Restores the interrupted run in `checkpoint`, offering the user the
+    /// chance to resume it.
+    pub fn restore(&mut self, checkpoint: &Checkpoint) {
+        self.end_attempt();
+        self.session.begin_attempt();
+        checkpoint.restore_onto(&mut self.run);
+        self.last_checkpoint = Some(checkpoint.clone());
+        self.mode = Box::new(recover::Recover::new(checkpoint, self.run.splits.len()));
+    }
+
     /// Gets the split position, if any.
     #[must_use]
     pub fn split_position(&self, index: usize) -> SplitPosition {
@@ -38,6 +105,139 @@ impl Presenter {
             .map_or(SplitPosition::default(), |x| x.split_position(index))
     }
 
+    /// Gets any prompt the current mode has for the user.
+    #[must_use]
+    pub fn prompt(&self) -> Option<prompt::Prompt<'_>> {
+        self.mode.prompt()
+    }
+
+    /// Borrows the command line (immutably), if we are at it.
+    #[must_use]
+    pub fn command_line(&self) -> Option<&command::Line> {
+        self.mode.command_line()
+    }
+
+    /// Borrows the search being typed (immutably), if any.
+    #[must_use]
+    pub fn search(&self) -> Option<&search::Search> {
+        self.mode.search()
+    }
+
+    /// Gets whether the split at `index` matches the search being typed or,
+    /// failing that, the last search accepted.
+    #[must_use]
+    pub fn is_search_match(&self, index: usize) -> bool {
+        let query = self
+            .search()
+            .map(search::Search::query)
+            .or(self.last_search.as_deref());
+        query.is_some_and(|q| search::is_match(&self.run.splits[index].name, q))
+    }
+
+    /// Borrows the current celebration, if there is one and it hasn't run
+    /// out.
+    #[must_use]
+    pub fn flash(&self) -> Option<&flash::Flash> {
+        self.flash.as_ref().filter(|f| f.is_live())
+    }
+
+    /// Borrows the summary of the run that just finished, if we are showing
+    /// one.
+    #[must_use]
+    pub fn summary(&self) -> Option<&finish::Summary> {
+        self.mode.summary()
+    }
+
+    /// Gets whether the current mode takes free text input, rather than
+    /// keybindings.
+    #[must_use]
+    pub fn takes_text(&self) -> bool {
+        self.mode.takes_text()
+    }
+
+    /// Gets any count or prefix typed ahead of a command.
+    #[must_use]
+    pub fn pending(&self) -> pending::Pending {
+        self.pending
+    }
+
+    /// Borrows the current picker (immutably), if we are picking.
+    #[must_use]
+    pub fn picker(&self) -> Option<&pick::Picker> {
+        self.mode.picker()
+    }
+
+    /// Starts picking a game to run.
+    pub fn pick_game(&mut self) {
+        let games = self.source.as_mut().map(|s| s.games()).unwrap_or_default();
+        self.mode = Box::new(pick::Picker::new(pick::Stage::Game, games));
+    }
+
+    /// Starts picking a category of the game with ID `game` to run.
+    pub fn pick_category(&mut self, game: &str) {
+        let Some(ref mut source) = self.source else {
+            return;
+        };
+        let name = source
+            .games()
+            .into_iter()
+            .find(|g| g.id == game)
+            .map_or_else(|| game.to_owned(), |g| g.name);
+        let choice = pick::Choice {
+            id: game.to_owned(),
+            name,
+        };
+        let categories = source.categories(game);
+        self.mode = Box::new(pick::Picker::new(pick::Stage::Category(choice), categories));
+    }
+
+    /// Loads the category with ID `category` of the game with ID `game` from
+    /// the source, and switches to it.
+    ///
+    /// Any attempt in progress ends, and we become inactive (or offer to
+    /// resume the loaded run, if it was interrupted).  Switching to another
+    /// game or category also ends the session and starts a new one, so that
+    /// each session's statistics cover only one category.
+    ///
+    /// # Errors
+    ///
+    /// Fails if there is no source, or the source fails to load the run; in
+    /// either case, nothing changes.
+    pub fn load(&mut self, game: &str, category: &str) -> Result<(), pick::Error> {
+        let source = self.source.as_mut().ok_or(pick::Error::NoSource)?;
+        let loaded = source.load(game, category)?;
+        self.stop_practice();
+        self.end_attempt();
+        self.before_pick = None;
+        self.last_search = None;
+        // The comparison chosen sticks across categories.
+        let against = self.run.against;
+        self.run = loaded.run;
+        self.run.against = against;
+        if !self.session.is_for(&self.run.metadata) {
+            self.end_session();
+            self.session = session::Session::new(chrono::Utc::now(), &self.run.metadata);
+        }
+        self.mode = Box::new(mode::Inactive);
+        if let Some(ref cp) = loaded.checkpoint {
+            self.restore(cp);
+        }
+        Ok(())
+    }
+
+    /// Gets whether we are practising part of the run.
+    #[must_use]
+    pub fn is_practising(&self) -> bool {
+        self.practice.is_some()
+    }
+
+    /// Gets the statistics for what we are practising, across every
+    /// session, if we are practising.
+    #[must_use]
+    pub fn practice_stats(&self) -> Option<Stats> {
+        self.practice.as_ref().map(|aside| aside.stats.clone())
+    }
+
     /// Gets whether the UI should be running.
     #[must_use]
     pub fn is_running(&self) -> bool {
@@ -58,20 +258,134 @@ impl Presenter {
         })
     }
 
-    /// Handles an event.  Returns true if the event changed the state.
+    /// Handles an event, applying any count or prefix typed ahead of it.
     pub fn handle_event(&mut self, e: &event::Event) {
+        use event::{Edit, Event};
+        let mut pending = std::mem::take(&mut self.pending);
+        match e {
+            Event::Edit(Edit::Add(digit)) if self.takes_count() => {
+                if pending.push_digit(*digit) {
+                    self.pending = pending;
+                } else {
+                    self.dispatch(e);
+                }
+            }
+            Event::Goto if !pending.goto => {
+                pending.goto = true;
+                self.pending = pending;
+            }
+            Event::Goto => self.dispatch(&Event::Cursor(cursor::Move {
+                motion: cursor::Motion::First,
+                count: pending.count,
+            })),
+            Event::Cursor(mv) => self.dispatch(&Event::Cursor(cursor::Move {
+                count: pending.count.or(mv.count),
+                ..*mv
+            })),
+            _ => self.dispatch(e),
+        }
+    }
+
+    /// Gets whether digits should go towards a count, rather than to the
+    /// mode: that is, whether there is a cursor to move, and no field being
+    /// edited.
+    fn takes_count(&self) -> bool {
+        self.mode.cursor().is_some() && self.editor().is_none_or(|e| e.field.is_none())
+    }
+
+    /// Sends `e` to the current mode, and then handles it globally if the
+    /// mode doesn't.
+    fn dispatch(&mut self, e: &event::Event) {
+        let golds = self.gold_splits();
         match self.mode.handle_event(e, &mut self.run) {
             mode::EventResult::Transition(new_mode) => self.transition(new_mode),
             mode::EventResult::NotHandled => self.handle_event_globally(e),
             mode::EventResult::Handled => (),
+            mode::EventResult::Finish => self.finish_run(),
+        }
+        self.celebrate_golds(&golds);
+        self.checkpoint();
+    }
+
+    /// Gets the indices of the splits that are currently golds.
+    fn gold_splits(&self) -> Vec<usize> {
+        (0..self.run.splits.len())
+            .filter(|i| self.run.is_gold(*i))
+            .collect()
+    }
+
+    /// Celebrates the first split to have become a gold since `before` (the
+    /// golds before the last event), if any.
+    ///
+    /// This doesn't interrupt the celebration of a personal best.
+    fn celebrate_golds(&mut self, before: &[usize]) {
+        if self
+            .flash()
+            .is_some_and(|f| f.target == flash::Target::PersonalBest)
+        {
+            return;
+        }
+        let new_gold = self.gold_splits().into_iter().find(|i| !before.contains(i));
+        if let Some(split) = new_gold {
+            let message = format!("Gold split: {}!", self.run.splits[split].name);
+            self.flash = Some(flash::Flash::new(flash::Target::Gold(split), message));
+        }
+    }
+
+    /// Checkpoints the current run, if it is in progress and has changed
+    /// since the last checkpoint, notifying any observers.
+    ///
+    /// Practice runs aren't checkpointed, so the last checkpoint of the full
+    /// run stands while we practise; neither is anything while picking or
+    /// typing a command or search, as the cursor is set aside.
+    fn checkpoint(&mut self) {
+        if self.is_practising()
+            || self.picker().is_some()
+            || self.command_line().is_some()
+            || self.search().is_some()
+            || !self.run.is_in_progress()
+        {
+            return;
+        }
+        let cursor = self.mode.cursor().map_or(0, cursor::Cursor::position);
+        let editor = self.mode.editor().map(Editor::checkpoint);
+        let Some(cp) = Checkpoint::new(&self.run, cursor, editor) else {
+            return;
+        };
+        if self.last_checkpoint.as_ref() == Some(&cp) {
+            return;
+        }
+        for o in &mut self.observers {
+            o.on_checkpoint(&self.run, &cp);
         }
+        self.last_checkpoint = Some(cp);
     }
 
     fn handle_event_globally(&mut self, e: &event::Event) {
         use event::Event;
         match e {
+            Event::Commit if self.command_line().is_some() => self.run_command(),
+            Event::Undo if self.command_line().is_some() => self.close_line(),
+            Event::Command => {
+                let line = command::Line::new(self.completions());
+                self.open_line(line);
+            }
+            Event::Commit if self.search().is_some() => self.accept_search(),
+            Event::Undo if self.search().is_some() => self.close_line(),
+            Event::Search => {
+                if let Some(cur) = self.mode.cursor().copied() {
+                    self.open_line(search::Search::new(cur));
+                }
+            }
+            Event::SearchAgain(dir) => self.search_again(*dir),
+            Event::Commit if self.picker().is_some() => self.choose(),
+            Event::Undo if self.picker().is_some() => self.pick_game(),
+            Event::Pick => self.toggle_picker(),
             Event::Commit => self.mode.commit(&mut self.run),
+            Event::NewRun if self.is_practising() => self.next_practice_attempt(),
             Event::NewRun => self.start_new_run(),
+            Event::Practice(_) if self.is_practising() => self.stop_practice(),
+            Event::Practice(scope) => self.start_practice(*scope),
             Event::Quit => self.quit(),
             _ => (),
         }
@@ -84,15 +398,336 @@ impl Presenter {
 
     /// Starts a new run, abandoning any previous run.
     fn start_new_run(&mut self) {
-        // TODO(@MattWindsor91): actually reset the run here.
+        if self.run.splits.is_empty() {
+            return;
+        }
+        self.end_attempt();
+        self.session.begin_attempt();
         self.run.reset();
+        self.run.start(chrono::Utc::now());
+        self.flash = None;
         let cur = cursor::Cursor::new(self.run.splits.len() - 1);
         // Don't commit the previous mode.
         self.mode = Box::new(nav::Nav::new(cur))
     }
 
+    /// Ends the current attempt, if there is one, notifying any observers
+    /// and returning its outcome.
+    fn end_attempt(&mut self) -> Option<run::Outcome> {
+        if !self.run.is_in_progress() {
+            return None;
+        }
+        let outcome = self.run.end(chrono::Utc::now());
+        self.last_checkpoint = None;
+        self.session.end_attempt(&self.run);
+        for o in &mut self.observers {
+            o.on_run_end(&self.run, outcome);
+        }
+        Some(outcome)
+    }
+
+    /// Handles the cursor moving past the end of the run.
+    ///
+    /// If the run is complete, it finishes; otherwise, we go back to
+    /// navigating, so the user can fill in the gaps.
+    fn finish_run(&mut self) {
+        let cur = self.mode.cursor().copied();
+        self.mode.commit(&mut self.run);
+        if self.run.is_completed() {
+            self.end_run();
+        } else if let Some(cur) = cur {
+            self.mode = Box::new(nav::Nav::new(cur));
+        }
+    }
+
+    /// Ends the current run, and shows a summary of it until the user starts
+    /// another.
+    ///
+    /// This doesn't commit the current mode.
+    fn end_run(&mut self) {
+        let pb = self.run.pb;
+        let Some(outcome) = self.end_attempt() else {
+            return;
+        };
+        let summary = finish::Summary::new(&self.run, outcome, pb, &self.session);
+        if summary.is_pb() {
+            self.run.pb = Some(summary.total);
+            let message = format!("New personal best: {}!", summary.total);
+            self.flash = Some(flash::Flash::new(flash::Target::PersonalBest, message));
+        }
+        self.mode = Box::new(finish::Finished::new(summary));
+    }
+
+    /// Picks the choice under the picker's cursor, if any.
+    fn choose(&mut self) {
+        let Some(picker) = self.picker() else {
+            return;
+        };
+        let Some(choice) = picker.selected().cloned() else {
+            return;
+        };
+        match picker.stage.clone() {
+            pick::Stage::Game => self.pick_category(&choice.id),
+            pick::Stage::Category(game) => {
+                if let Err(e) = self.load(&game.id, &choice.id) {
+                    // Loading failed, so we're still picking.
+                    if let Some(picker) = self.picker().map(|p| p.with_error(&e)) {
+                        self.mode = Box::new(picker);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Opens `line` (the command line or a search), setting aside the
+    /// current mode.
+    fn open_line(&mut self, line: impl mode::Mode + 'static) {
+        let before = std::mem::replace(&mut self.mode, Box::new(line));
+        self.before_line = Some(before);
+    }
+
+    /// Leaves the command line or search, going back to what we were doing.
+    fn close_line(&mut self) {
+        self.mode = self
+            .before_line
+            .take()
+            .unwrap_or_else(|| Box::new(mode::Inactive));
+    }
+
+    /// Gets the names the command line can complete.
+    fn completions(&mut self) -> command::Completions {
+        let game = &self.run.metadata.game_id;
+        let categories = self.source.as_mut().map_or_else(Vec::new, |s| {
+            s.categories(game).into_iter().map(|c| c.id).collect()
+        });
+        let splits = self.run.splits.iter().map(|s| s.name.clone()).collect();
+        command::Completions { categories, splits }
+    }
+
+    /// Runs the command typed at the command line.
+    ///
+    /// If the command fails, we go back to the command line to report why.
+    fn run_command(&mut self) {
+        let Some(line) = self.command_line().cloned() else {
+            return;
+        };
+        self.close_line();
+        if let Err(e) = line.input().parse().and_then(|c| self.execute(c)) {
+            self.open_line(line.with_error(&e));
+        }
+    }
+
+    /// Accepts the search being typed, moving to its match (if any) and
+    /// remembering it for later.
+    ///
+    /// An empty search repeats the last search instead.
+    fn accept_search(&mut self) {
+        let Some(search) = self.search().cloned() else {
+            return;
+        };
+        self.close_line();
+        if search.query().is_empty() {
+            self.search_again(search::Direction::Forward);
+            return;
+        }
+        self.last_search = Some(search.query().to_owned());
+        if let Some(target) = search.target() {
+            self.move_to(target);
+        }
+    }
+
+    /// Moves to the next match of the last search in direction `dir`.
+    fn search_again(&mut self, dir: search::Direction) {
+        let Some(ref query) = self.last_search else {
+            return;
+        };
+        let Some(cur) = self.mode.cursor() else {
+            return;
+        };
+        if let Some(target) = search::find(&self.run, query, cur.position(), dir) {
+            self.move_to(target);
+        }
+    }
+
+    /// Moves the cursor to the split at `index`, through the current mode.
+    fn move_to(&mut self, index: usize) {
+        self.dispatch(&event::Event::Cursor(cursor::Move {
+            motion: cursor::Motion::First,
+            count: Some(index + 1),
+        }));
+    }
+
+    /// Executes the command `cmd`.
+    fn execute(&mut self, cmd: command::Command) -> command::Result<()> {
+        use command::{Command, Error};
+        match cmd {
+            Command::Write => {
+                if self.is_practising() {
+                    return Err(Error::Practising);
+                }
+                if !self.run.is_in_progress() {
+                    return Err(Error::NotRunning);
+                }
+                self.mode.commit(&mut self.run);
+                self.end_run();
+            }
+            Command::Edit(category) => {
+                let game = self.run.metadata.game_id.clone();
+                self.load(&game, &category).map_err(Error::Load)?;
+            }
+            Command::Comparison(name) => {
+                let against = Against::from_name(&name).ok_or(Error::UnknownComparison(name))?;
+                self.run.against = against;
+                if let Some(ref mut aside) = self.practice {
+                    aside.run.against = against;
+                }
+            }
+            Command::Goto(name) => {
+                let split = self.run.find_split(&name).ok_or(Error::NoSuchSplit(name))?;
+                if self.mode.cursor().is_none() {
+                    return Err(Error::NoCursor);
+                }
+                self.move_to(split);
+            }
+            Command::Reset => self.handle_event_globally(&event::Event::NewRun),
+            Command::Quit => self.quit(),
+        }
+        Ok(())
+    }
+
+    /// Starts picking a category of the current game, or, if we are picking
+    /// mid-session, goes back to what we were doing.
+    fn toggle_picker(&mut self) {
+        if self.picker().is_some() {
+            if let Some(mode) = self.before_pick.take() {
+                self.mode = mode;
+            }
+            return;
+        }
+        if self.source.is_none() {
+            return;
+        }
+        self.stop_practice();
+        let game = self.run.metadata.game_id.clone();
+        let before = std::mem::replace(&mut self.mode, Box::new(mode::Inactive));
+        self.before_pick = Some(before);
+        self.pick_category(&game);
+    }
+
+    /// Starts practising `scope` around the cursor, setting aside the full
+    /// run.
+    fn start_practice(&mut self, scope: practice::Scope) {
+        let Some(cur) = self.mode.cursor().copied() else {
+            return;
+        };
+        let target = match scope {
+            practice::Scope::Split => Some(Target::Split(cur.position())),
+            practice::Scope::Group => self
+                .run
+                .innermost_group_of(cur.position())
+                .map(Target::Group),
+        };
+        let Some((target, il)) = target.and_then(|t| Some((t, self.run.il(t)?))) else {
+            return;
+        };
+        let start = match target {
+            Target::Split(split) => split,
+            Target::Group(group) => self.run.groups[group].start,
+        };
+        let stats = self.load_practice_stats(&il.metadata);
+
+        self.mode.commit(&mut self.run);
+        let run = std::mem::replace(&mut self.run, il);
+        self.practice = Some(practice::SetAside {
+            run,
+            cursor: cur,
+            start,
+            stats,
+        });
+        self.begin_practice_attempt();
+    }
+
+    /// Loads the practice statistics for the IL run with metadata `meta`,
+    /// across every session, from the source.
+    ///
+    /// Without a source, only this session's statistics are available.
+    fn load_practice_stats(&mut self, meta: &run::Metadata) -> Stats {
+        match self.source {
+            Some(ref mut source) => source.practice_stats(&meta.game_id, &meta.category_id),
+            None => self
+                .session
+                .practice_stats(&meta.game_id, &meta.category_id),
+        }
+    }
+
+    /// Starts a new attempt at whatever we are practising.
+    fn begin_practice_attempt(&mut self) {
+        self.run.reset();
+        self.run.attempt = self.practice_stats().unwrap_or_default().attempts + 1;
+        self.run.start(chrono::Utc::now());
+        self.mode = Box::new(practice::Practice::new(&self.run));
+    }
+
+    /// Ends the current practice attempt, recording it if anything in it was
+    /// timed.
+    fn end_practice_attempt(&mut self) {
+        if !self.run.is_in_progress() {
+            return;
+        }
+        // As with full runs, don't commit the mode.
+        self.run.end(chrono::Utc::now());
+        if let Some(attempt) = Attempt::from_run(&self.run) {
+            if let Some(ref mut aside) = self.practice {
+                aside.stats.add(&attempt);
+            }
+            self.session.end_practice(&attempt);
+            for o in &mut self.observers {
+                o.on_practice_end(&attempt);
+            }
+        }
+    }
+
+    /// Ends the current practice attempt and starts another.
+    fn next_practice_attempt(&mut self) {
+        self.end_practice_attempt();
+        self.begin_practice_attempt();
+    }
+
+    /// Stops practising, if we are, and returns to the full run.
+    ///
+    /// Any best times set while practising carry over to the full run.
+    fn stop_practice(&mut self) {
+        let Some(aside) = self.practice.take() else {
+            return;
+        };
+        self.end_practice_attempt();
+        let il = std::mem::replace(&mut self.run, aside.run);
+        self.run.adopt_bests(&il, aside.start);
+        self.mode = Box::new(nav::Nav::new(aside.cursor));
+    }
+
     /// Start the process of quitting.
+    ///
+    /// Quitting while offered an interrupted run neither resumes nor
+    /// abandons it, so its checkpoint stays for next time.
     fn quit(&mut self) {
-        self.transition(Box::new(mode::Quitting))
+        let recovering = self.mode.is_recovering();
+        self.stop_practice();
+        self.transition(Box::new(mode::Quitting));
+        if !recovering {
+            self.end_attempt();
+        }
+        self.end_session();
+    }
+
+    /// Ends the session, notifying any observers.
+    fn end_session(&mut self) {
+        if self.session.has_ended() {
+            return;
+        }
+        self.session.finish(chrono::Utc::now());
+        for o in &mut self.observers {
+            o.on_session_end(&self.session);
+        }
     }
 }
@@ -0,0 +1,320 @@
+//! The vi-style command line ([Line]), and the [Command]s it runs.
+
+use super::{
+    event::{Edit, Event},
+    mode::{EventResult, Mode},
+    pick,
+};
+use crate::model::{run::Run, split::Against};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// A command typed at the command line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Save the run in progress to the database, ending it.
+    Write,
+    /// Switch to the given category of the current game.
+    Edit(String),
+    /// Compare splits against the named comparison (see [`Against::ALL`]).
+    Comparison(String),
+    /// Move the cursor to the split with the given name.
+    Goto(String),
+    /// Reset the run, starting a new attempt.
+    Reset,
+    /// Quit zombiesplit.
+    Quit,
+}
+
+/// The full names of every command, for completion.
+const COMMANDS: &[&str] = &["comparison", "edit", "goto", "quit", "reset", "write"];
+
+impl FromStr for Command {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (name, arg) = split_command(s.trim());
+        let arg = || {
+            Some(arg.trim())
+                .filter(|a| !a.is_empty())
+                .map(ToOwned::to_owned)
+                .ok_or(Error::MissingArgument)
+        };
+        match name {
+            "w" | "write" => Ok(Self::Write),
+            "e" | "edit" => Ok(Self::Edit(arg()?)),
+            "comparison" => Ok(Self::Comparison(arg()?)),
+            "goto" => Ok(Self::Goto(arg()?)),
+            "reset" => Ok(Self::Reset),
+            "q" | "quit" => Ok(Self::Quit),
+            "" => Err(Error::Empty),
+            _ => Err(Error::Unknown(name.to_owned())),
+        }
+    }
+}
+
+/// Splits `s` into a command name and the rest of the line.
+fn split_command(s: &str) -> (&str, &str) {
+    s.split_once(' ').unwrap_or((s, ""))
+}
+
+/// Names that can be completed at the command line, other than commands.
+#[derive(Clone, Debug, Default)]
+pub struct Completions {
+    /// IDs of the categories of the current game.
+    pub categories: Vec<String>,
+    /// Names of the splits in the current run.
+    pub splits: Vec<String>,
+}
+
+/// Mode for typing in a command.
+#[derive(Clone, Debug)]
+pub struct Line {
+    /// The text typed so far, not including the leading `:`.
+    input: String,
+    /// Any error from the last command run from this line.
+    error: Option<String>,
+    /// The names available for completion.
+    completions: Completions,
+    /// If we are cycling through ambiguous completions, the candidates and
+    /// the index of the next one.
+    cycle: Option<(Vec<String>, usize)>,
+}
+
+impl Mode for Line {
+    fn handle_event(&mut self, e: &Event, _: &mut Run) -> EventResult {
+        match e {
+            Event::Edit(Edit::Insert(c)) => self.edit(|input| input.push(*c)),
+            Event::Edit(Edit::Remove) => self.edit(|input| {
+                input.pop();
+            }),
+            Event::Complete => {
+                self.complete();
+                EventResult::Handled
+            }
+            _ => EventResult::NotHandled,
+        }
+    }
+
+    fn command_line(&self) -> Option<&Line> {
+        Some(self)
+    }
+
+    fn takes_text(&self) -> bool {
+        true
+    }
+}
+
+impl Line {
+    /// Constructs an empty command line, completing from `completions`.
+    #[must_use]
+    pub fn new(completions: Completions) -> Self {
+        Self {
+            input: String::new(),
+            error: None,
+            completions,
+            cycle: None,
+        }
+    }
+
+    /// Copies this line, reporting that its command failed with `error`.
+    #[must_use]
+    pub fn with_error(&self, error: &dyn std::error::Error) -> Self {
+        Self {
+            error: Some(error.to_string()),
+            cycle: None,
+            ..self.clone()
+        }
+    }
+
+    /// Gets the text typed so far, not including the leading `:`.
+    #[must_use]
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Gets the error from the last command run from this line, if any.
+    #[must_use]
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    fn edit(&mut self, f: impl FnOnce(&mut String)) -> EventResult {
+        f(&mut self.input);
+        self.error = None;
+        self.cycle = None;
+        EventResult::Handled
+    }
+
+    /// Completes the word being typed.
+    ///
+    /// This completes as much as all candidates have in common; once there
+    /// is nothing in common left to complete, repeated completion cycles
+    /// through the candidates.
+    fn complete(&mut self) {
+        self.error = None;
+        if let Some((candidates, next)) = self.cycle.take() {
+            self.replace_word(&candidates[next]);
+            let next = (next + 1) % candidates.len();
+            self.cycle = Some((candidates, next));
+            return;
+        }
+
+        let (start, candidates) = self.candidates();
+        let word = &self.input[start..];
+        let candidates: Vec<String> = candidates
+            .into_iter()
+            .filter(|c| starts_with_ignore_case(c, word))
+            .collect();
+        match candidates.as_slice() {
+            [] => (),
+            [only] => {
+                let only = only.clone();
+                self.replace_word(&only);
+                if start == 0 {
+                    self.input.push(' ');
+                }
+            }
+            [first, ..] => {
+                let common = common_prefix(&candidates);
+                if common.len() > word.len() {
+                    self.replace_word(&common);
+                } else {
+                    let first = first.clone();
+                    self.replace_word(&first);
+                    self.cycle = Some((candidates, 1));
+                }
+            }
+        }
+    }
+
+    /// Gets where the word being completed starts, and what it could be.
+    fn candidates(&self) -> (usize, Vec<String>) {
+        let Some((name, _)) = self.input.split_once(' ') else {
+            return (0, COMMANDS.iter().map(|c| (*c).to_owned()).collect());
+        };
+        let candidates = match name {
+            "e" | "edit" => self.completions.categories.clone(),
+            "goto" => self.completions.splits.clone(),
+            "comparison" => Against::ALL.iter().map(|(_, n)| (*n).to_owned()).collect(),
+            _ => vec![],
+        };
+        (name.len() + 1, candidates)
+    }
+
+    /// Replaces the word being completed with `with`.
+    fn replace_word(&mut self, with: &str) {
+        let start = self.input.find(' ').map_or(0, |i| i + 1);
+        self.input.truncate(start);
+        self.input.push_str(with);
+    }
+}
+
+/// Gets whether `s` starts with `prefix`, ignoring case.
+fn starts_with_ignore_case(s: &str, prefix: &str) -> bool {
+    s.to_lowercase().starts_with(&prefix.to_lowercase())
+}
+
+/// Gets the longest prefix all of `strings` share, ignoring case (but taking
+/// the case of the first string).
+fn common_prefix(strings: &[String]) -> String {
+    let Some((first, rest)) = strings.split_first() else {
+        return String::new();
+    };
+    let mut len = first.len();
+    for s in rest {
+        len = first
+            .char_indices()
+            .zip(s.chars())
+            .take_while(|((_, a), b)| a.to_lowercase().eq(b.to_lowercase()))
+            .last()
+            .map_or(0, |((i, a), _)| i + a.len_utf8())
+            .min(len);
+    }
+    first[..len].to_owned()
+}
+
+/// Enumeration of errors occurring when parsing or running commands.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("no command given")]
+    Empty,
+    #[error("unknown command '{0}'")]
+    Unknown(String),
+    #[error("this command needs an argument")]
+    MissingArgument,
+    #[error("no run in progress")]
+    NotRunning,
+    #[error("can't save a run while practising")]
+    Practising,
+    #[error("no split named '{0}'")]
+    NoSuchSplit(String),
+    #[error("nowhere to move to")]
+    NoCursor,
+    #[error("no comparison named '{0}' (try 'pb', 'best', or 'none')")]
+    UnknownComparison(String),
+    #[error("couldn't switch category: {0}")]
+    Load(pick::Error),
+}
+
+/// Shorthand for results over [Error].
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests parsing commands, including short forms and arguments with
+    /// spaces in.
+    #[test]
+    fn parse() {
+        assert_eq!("w".parse::<Command>().unwrap(), Command::Write);
+        assert_eq!(
+            " goto Palmtree Panic 1 ".parse::<Command>().unwrap(),
+            Command::Goto("Palmtree Panic 1".to_owned())
+        );
+        assert_eq!(
+            "e btg".parse::<Command>().unwrap(),
+            Command::Edit("btg".to_owned())
+        );
+        assert!(matches!(
+            "edit".parse::<Command>(),
+            Err(Error::MissingArgument)
+        ));
+        assert!(matches!("wq".parse::<Command>(), Err(Error::Unknown(_))));
+    }
+
+    /// Tests completing command names and arguments, including cycling
+    /// through ambiguous completions.
+    #[test]
+    fn complete() {
+        let mut line = Line::new(Completions {
+            categories: vec![],
+            splits: vec![
+                "Palmtree Panic 1".to_owned(),
+                "Palmtree Panic 2".to_owned(),
+                "Collision Chaos 1".to_owned(),
+            ],
+        });
+        let mut run = Run::default();
+        let mut type_str = |line: &mut Line, s: &str| {
+            for c in s.chars() {
+                line.handle_event(&Event::Edit(Edit::Insert(c)), &mut run);
+            }
+        };
+
+        type_str(&mut line, "go");
+        line.complete();
+        assert_eq!(line.input(), "goto ");
+
+        type_str(&mut line, "palm");
+        line.complete();
+        assert_eq!(line.input(), "goto Palmtree Panic ");
+        line.complete();
+        assert_eq!(line.input(), "goto Palmtree Panic 1");
+        line.complete();
+        assert_eq!(line.input(), "goto Palmtree Panic 2");
+        line.complete();
+        assert_eq!(line.input(), "goto Palmtree Panic 1");
+    }
+}
@@ -3,7 +3,7 @@
 use std::cmp::Ordering;
 
 /// A bounded cursor.
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 pub struct Cursor {
     /// The current position.
     pos: usize,
@@ -18,6 +18,16 @@ impl Cursor {
         Self { pos: 0, max }
     }
 
+    /// Creates a new cursor at position `pos` (clamped to `max`), with the
+    /// given maximum index.
+    #[must_use]
+    pub fn with_position(pos: usize, max: usize) -> Self {
+        Self {
+            pos: pos.min(max),
+            max,
+        }
+    }
+
     /// Gets the current cursor position.
     #[must_use]
     pub fn position(&self) -> usize {
@@ -56,24 +66,81 @@ impl Cursor {
         }
     }
 
-    /// Moves the cursor in the direction of `m` `multiplier` times.
+    /// Performs the move `mv`, using `stops` (the sorted positions at which
+    /// groups start) for group motions.
     /// Returns the absolute amount by which the cursor moved.
-    pub fn move_by(&mut self, m: Motion, multiplier: usize) -> usize {
-        // TODO(@MattWindsor91): multipliers
-        match m {
-            Motion::Up => self.move_up(multiplier),
-            Motion::Down => self.move_down(multiplier),
+    pub fn move_by(&mut self, mv: Move, stops: &[usize]) -> usize {
+        let before = self.pos;
+        let count = mv.count.unwrap_or(1);
+        match mv.motion {
+            Motion::Up => {
+                self.move_up(count);
+            }
+            Motion::Down => {
+                self.move_down(count);
+            }
+            // Counts on these are 1-based split numbers, as in vi.
+            Motion::First => self.pos = mv.count.map_or(0, |n| n.saturating_sub(1)),
+            Motion::Last => self.pos = mv.count.map_or(self.max, |n| n.saturating_sub(1)),
+            Motion::PrevGroup => {
+                for _ in 0..count {
+                    self.pos = stops
+                        .iter()
+                        .rev()
+                        .find(|&&s| s < self.pos)
+                        .map_or(0, |s| *s);
+                }
+            }
+            Motion::NextGroup => {
+                for _ in 0..count {
+                    self.pos = stops
+                        .iter()
+                        .find(|&&s| s > self.pos)
+                        .map_or(self.max, |s| *s);
+                }
+            }
         }
+        self.pos = self.pos.min(self.max);
+        self.pos.abs_diff(before)
     }
 }
 
 /// A cursor motion.
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Motion {
     /// Move the cursor up.
     Up,
     /// Move the cursor down.
     Down,
+    /// Move the cursor to the first split (or, with a count, to that split).
+    First,
+    /// Move the cursor to the last split (or, with a count, to that split).
+    Last,
+    /// Move the cursor to the start of the previous group.
+    PrevGroup,
+    /// Move the cursor to the start of the next group.
+    NextGroup,
+}
+
+/// A motion, along with the count given to it (if any).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Move {
+    /// The motion.
+    pub motion: Motion,
+    /// The count, if any.
+    ///
+    /// Most motions repeat this many times, but [`Motion::First`] and
+    /// [`Motion::Last`] instead move to the split numbered by the count.
+    pub count: Option<usize>,
+}
+
+impl From<Motion> for Move {
+    fn from(motion: Motion) -> Self {
+        Self {
+            motion,
+            count: None,
+        }
+    }
 }
 
 /// Relative positions of splits to cursors.
@@ -93,3 +160,41 @@ impl Default for SplitPosition {
         Self::Coming
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mv(motion: Motion, count: Option<usize>) -> Move {
+        Move { motion, count }
+    }
+
+    /// Tests counted motions, including those that run off either end.
+    #[test]
+    fn move_by_counts() {
+        let mut cur = Cursor::with_position(2, 9);
+        assert_eq!(cur.move_by(mv(Motion::Down, Some(5)), &[]), 5);
+        assert_eq!(cur.position(), 7);
+        assert_eq!(cur.move_by(mv(Motion::Down, Some(5)), &[]), 2);
+        assert_eq!(cur.move_by(mv(Motion::First, None), &[]), 9);
+        assert_eq!(cur.move_by(mv(Motion::Last, Some(4)), &[]), 3);
+        assert_eq!(cur.position(), 3);
+        assert_eq!(cur.move_by(mv(Motion::Last, Some(40)), &[]), 6);
+        assert_eq!(cur.move_by(mv(Motion::Up, None), &[]), 1);
+    }
+
+    /// Tests moving between groups.
+    #[test]
+    fn move_by_groups() {
+        let stops = [0, 3, 6];
+        let mut cur = Cursor::with_position(4, 9);
+        cur.move_by(mv(Motion::PrevGroup, None), &stops);
+        assert_eq!(cur.position(), 3);
+        cur.move_by(mv(Motion::PrevGroup, None), &stops);
+        assert_eq!(cur.position(), 0);
+        cur.move_by(mv(Motion::NextGroup, Some(2)), &stops);
+        assert_eq!(cur.position(), 6);
+        cur.move_by(mv(Motion::NextGroup, None), &stops);
+        assert_eq!(cur.position(), 9);
+    }
+}
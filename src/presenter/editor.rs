@@ -5,10 +5,11 @@ use std::fmt::{Display, Formatter};
 use super::{
     cursor::{self, Cursor},
     event::{Edit, Event},
-    mode::{EventResult, Inactive, Mode},
+    mode::{EventResult, Mode},
     nav::Nav,
 };
 use crate::model::{
+    checkpoint,
     run::Run,
     time::{self, position},
 };
@@ -26,13 +27,13 @@ pub struct Editor {
 }
 
 impl Mode for Editor {
-    fn handle_event(&mut self, e: &Event, _: &mut Run) -> EventResult {
+    fn handle_event(&mut self, e: &Event, r: &mut Run) -> EventResult {
         match e {
             Event::Undo => self.undo(),
             Event::Delete => self.delete(),
             Event::Edit(d) => self.edit(d),
             Event::EnterField(f) => self.enter_field(*f),
-            Event::Cursor(c) => self.move_cursor(*c),
+            Event::Cursor(mv) => self.move_cursor(*mv, r),
             _ => EventResult::NotHandled,
         }
     }
@@ -73,6 +74,26 @@ impl Editor {
         }
     }
 
+    /// Constructs an editor with the given cursor, restoring its state from
+    /// the checkpoint `state`.
+    #[must_use]
+    pub fn restore(cur: Cursor, state: &checkpoint::Editor) -> Self {
+        Self {
+            cur,
+            time: state.time,
+            field: state.field.as_ref().map(Field::restore),
+        }
+    }
+
+    /// Takes a checkpoint of this editor's state.
+    #[must_use]
+    pub fn checkpoint(&self) -> checkpoint::Editor {
+        checkpoint::Editor {
+            time: self.time,
+            field: self.field.as_ref().map(Field::checkpoint),
+        }
+    }
+
     /// Enters the named field, committing any edits on any current field.
     #[must_use]
     pub fn enter_field(&mut self, field: position::Name) -> EventResult {
@@ -112,16 +133,15 @@ impl Editor {
         }
     }
 
-    /// Performs the given cursor motion.
+    /// Performs the given cursor move within `r`.
     #[must_use]
-    pub fn move_cursor(&mut self, motion: cursor::Motion) -> EventResult {
+    pub fn move_cursor(&mut self, mv: cursor::Move, r: &Run) -> EventResult {
         // Need to copy the cursor, so that the editor commits to the
         // right location.
         let mut cur = self.cur;
-        let amt = cur.move_by(motion, 1);
-        if amt != 1 && matches!(motion, cursor::Motion::Down) {
-            // End of run
-            EventResult::Transition(Box::new(Inactive))
+        let amt = cur.move_by(mv, &r.group_starts());
+        if amt == 0 && mv.motion == cursor::Motion::Down {
+            EventResult::Finish
         } else {
             Nav::transition(cur)
         }
@@ -146,6 +166,24 @@ impl Field {
         }
     }
 
+    /// Restores a field editor from the checkpoint `state`.
+    #[must_use]
+    pub fn restore(state: &checkpoint::Field) -> Self {
+        let mut field = Self::new(state.position);
+        field.string.push_str(&state.digits);
+        field.string.truncate(field.max_digits());
+        field
+    }
+
+    /// Takes a checkpoint of this field editor's state.
+    #[must_use]
+    pub fn checkpoint(&self) -> checkpoint::Field {
+        checkpoint::Field {
+            position: self.position,
+            digits: self.string.clone(),
+        }
+    }
+
     /// Gets this editor's position.
     #[must_use]
     pub fn position(&self) -> position::Name {
@@ -156,6 +194,7 @@ impl Field {
         match e {
             Edit::Add(x) => self.add(*x),
             Edit::Remove => self.remove(),
+            Edit::Insert(_) => false,
         }
     }
 
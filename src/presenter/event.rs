@@ -4,22 +4,43 @@ use crate::model::time::position;
 /// A high-level event.
 ///
 /// The semantics of events depends on the current editing mode.
+#[derive(Debug, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum Event {
     /// Undo something (the exact thing depends on the mode).
     Undo,
     /// Delete something completely (the exact thing depends on the mode).
     Delete,
+    /// Skip the split under the cursor, or un-skip it if it is already
+    /// skipped.
+    Skip,
     /// Commits whatever action is currently pending without moving the cursor.
     Commit,
     /// Start editing a field at a particular position.
     EnterField(position::Name),
     /// Perform an event on the currently open editor.
     Edit(Edit),
-    /// Start a new run.
+    /// Start a new run (or, when practising, a new practice attempt).
     NewRun,
+    /// Pick another category (or game) to run, or stop picking if we
+    /// already are.
+    Pick,
+    /// Start practising the given scope around the cursor, or stop practising
+    /// if we already are.
+    Practice(super::practice::Scope),
     /// Move the cursor.
-    Cursor(super::cursor::Motion),
+    Cursor(super::cursor::Move),
+    /// Go to a split: pressed twice (`gg` in vi), this moves to the first
+    /// split, or the split numbered by any count given.
+    Goto,
+    /// Open the command line.
+    Command,
+    /// Start searching for a split by name.
+    Search,
+    /// Move to the next match of the last search, in the given direction.
+    SearchAgain(super::search::Direction),
+    /// Complete the word being typed.
+    Complete,
     /// Quit the program.
     Quit,
 }
@@ -32,13 +53,22 @@ impl Event {
     pub fn digit(digit: u8) -> Self {
         Self::Edit(Edit::Add(digit))
     }
+
+    /// Shorthand for producing an uncounted cursor event.
+    #[must_use]
+    pub fn motion(motion: super::cursor::Motion) -> Self {
+        Self::Cursor(motion.into())
+    }
 }
 
 /// An edit event.
+#[derive(Debug, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum Edit {
     /// Add the given digit to the current editor.
     Add(u8),
     /// Remove the last item (for instance, a digit from the current editor.
     Remove,
+    /// Insert the given character into the current text input.
+    Insert(char),
 }
@@ -0,0 +1,142 @@
+//! The [Finished] mode, and the [Summary] of the run it shows.
+
+use super::{
+    mode::Mode,
+    prompt::{self, Prompt},
+};
+use crate::model::{
+    pace::Delta,
+    run::{Outcome, Run},
+    session::Session,
+    time::Time,
+};
+/// A summary of a run that has just finished.
+#[derive(Clone, Debug)]
+pub struct Summary {
+    /// How the run ended.
+    pub outcome: Outcome,
+    /// The final time of the run.
+    pub total: Time,
+    /// The personal best before this run, if any.
+    pub pb: Option<Time>,
+    /// The number of gold splits in the run.
+    pub golds: usize,
+    /// The number of attempts so far this session.
+    pub attempts: usize,
+    /// The number of attempts completed so far this session.
+    pub completions: usize,
+    /// The best completed time this session, if any.
+    pub session_best: Option<Time>,
+}
+
+impl Summary {
+    /// Summarises `run`, which ended with outcome `outcome`, against the
+    /// personal best `pb` and the session `session`.
+    ///
+    /// The session should already include the run.
+    #[must_use]
+    pub fn new(run: &Run, outcome: Outcome, pb: Option<Time>, session: &Session) -> Self {
+        Self {
+            outcome,
+            total: run.total(),
+            pb,
+            golds: run.golds(),
+            attempts: session.attempts,
+            completions: session.completions,
+            session_best: session.best,
+        }
+    }
+
+    /// Gets whether the run was completed.
+    #[must_use]
+    pub fn is_completed(&self) -> bool {
+        self.outcome == Outcome::Completed
+    }
+
+    /// Gets whether the run was completed in a new personal best.
+    #[must_use]
+    pub fn is_pb(&self) -> bool {
+        self.is_completed() && self.pb.is_none_or(|pb| self.total < pb)
+    }
+
+    /// Gets the difference between the run's final time and the previous
+    /// personal best, if the run was completed and there was one.
+    #[must_use]
+    pub fn pb_delta(&self) -> Option<Delta> {
+        let pb = self.pb.filter(|_| self.is_completed())?;
+        Some(Delta::between(self.total, pb))
+    }
+}
+
+/// Mode for when a run has just finished.
+///
+/// This holds on to the run's summary, and the run itself, until the user
+/// starts a new run.
+pub struct Finished {
+    /// The summary of the finished run.
+    summary: Summary,
+}
+
+impl Mode for Finished {
+    fn summary(&self) -> Option<&Summary> {
+        Some(&self.summary)
+    }
+
+    fn prompt(&self) -> Option<Prompt<'_>> {
+        let text = if self.summary.is_pb() {
+            "New personal best!"
+        } else {
+            "Run over."
+        };
+        Some(Prompt::new(text).with_hints(prompt::NEW_RUN))
+    }
+}
+
+impl Finished {
+    /// Constructs a finished mode showing `summary`.
+    #[must_use]
+    pub fn new(summary: Summary) -> Self {
+        Self { summary }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    fn summary(total: u32, pb: Option<u32>, outcome: Outcome) -> Summary {
+        let time = |ms| Time::try_from(ms).unwrap();
+        Summary {
+            outcome,
+            total: time(total),
+            pb: pb.map(time),
+            golds: 0,
+            attempts: 1,
+            completions: 1,
+            session_best: None,
+        }
+    }
+
+    /// Tests working out personal bests and deltas against them.
+    #[test]
+    fn pb_delta() {
+        let first = summary(60_000, None, Outcome::Completed);
+        assert!(first.is_pb());
+        assert_eq!(first.pb_delta(), None);
+
+        let slower = summary(61_500, Some(60_000), Outcome::Completed);
+        assert!(!slower.is_pb());
+        assert_eq!(
+            slower.pb_delta(),
+            Some(Delta {
+                ahead: false,
+                time: Time::try_from(1_500).unwrap()
+            })
+        );
+
+        let reset = summary(30_000, Some(60_000), Outcome::Reset(3));
+        assert!(!reset.is_pb());
+        assert_eq!(reset.pb_delta(), None);
+    }
+}
@@ -0,0 +1,51 @@
+//! Short-lived celebrations of gold splits and personal bests.
+
+use std::time::{Duration, Instant};
+
+/// How long a flash lasts.
+const LENGTH: Duration = Duration::from_secs(3);
+
+/// What a flash is celebrating.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Target {
+    /// The split at the given index beat its best segment.
+    Gold(usize),
+    /// The run was completed in a new personal best.
+    PersonalBest,
+}
+
+/// A celebration, which the view can flash and show a message for until it
+/// runs out.
+#[derive(Clone, Debug)]
+pub struct Flash {
+    /// What is being celebrated.
+    pub target: Target,
+    /// The message to show.
+    pub message: String,
+    /// When the flash started.
+    since: Instant,
+}
+
+impl Flash {
+    /// Starts a flash celebrating `target` with message `message`.
+    #[must_use]
+    pub fn new(target: Target, message: String) -> Self {
+        Self {
+            target,
+            message,
+            since: Instant::now(),
+        }
+    }
+
+    /// Gets how long the flash has been going.
+    #[must_use]
+    pub fn age(&self) -> Duration {
+        self.since.elapsed()
+    }
+
+    /// Gets whether the flash is still going.
+    #[must_use]
+    pub fn is_live(&self) -> bool {
+        self.age() < LENGTH
+    }
+}
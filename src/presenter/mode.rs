@@ -2,7 +2,10 @@
 
 use crate::model::run::Run;
 
-use super::{cursor::Cursor, editor::Editor, event::Event};
+use super::{
+    command::Line, cursor::Cursor, editor::Editor, event::Event, finish::Summary, pick::Picker,
+    prompt::Prompt, search::Search,
+};
 
 /// Trait for presenter modes.
 ///
@@ -34,6 +37,43 @@ pub trait Mode {
         None
     }
 
+    /// If this mode is picking a game or category, immutably borrows the
+    /// picker.
+    fn picker(&self) -> Option<&Picker> {
+        None
+    }
+
+    /// If this mode is the command line, immutably borrows it.
+    fn command_line(&self) -> Option<&Line> {
+        None
+    }
+
+    /// If this mode is typing a search, immutably borrows the search.
+    fn search(&self) -> Option<&Search> {
+        None
+    }
+
+    /// If this mode is showing a finished run, immutably borrows its
+    /// summary.
+    fn summary(&self) -> Option<&Summary> {
+        None
+    }
+
+    /// Does this mode take free text input (rather than keybindings)?
+    fn takes_text(&self) -> bool {
+        false
+    }
+
+    /// If this mode wants to prompt the user for something, gets the prompt.
+    fn prompt(&self) -> Option<Prompt<'_>> {
+        None
+    }
+
+    /// Is this mode offering up an interrupted run for resumption?
+    fn is_recovering(&self) -> bool {
+        false
+    }
+
     /// Is zombiesplit running while this mode is active?
     fn is_running(&self) -> bool {
         true
@@ -62,6 +102,9 @@ pub enum EventResult {
     Handled,
     /// The event caused a transition to another mode.
     Transition(Box<dyn Mode>),
+    /// The event moved past the end of the run, which should finish if it
+    /// is complete.
+    Finish,
 }
 
 impl EventResult {
@@ -17,10 +17,11 @@ pub struct Nav {
 impl Mode for Nav {
     fn handle_event(&mut self, e: &Event, r: &mut Run) -> EventResult {
         match e {
-            Event::Cursor(c) => self.move_cursor(*c),
+            Event::Cursor(mv) => self.move_cursor(*mv, r),
             Event::EnterField(f) => self.enter_field(*f),
             Event::Undo => self.undo(r),
             Event::Delete => self.delete(r),
+            Event::Skip => self.skip(r),
             _ => EventResult::NotHandled,
         }
     }
@@ -57,10 +58,20 @@ impl Nav {
         EventResult::Handled
     }
 
-    /// Moves the state cursor according to `c`, if possible.
-    fn move_cursor(&mut self, motion: cursor::Motion) -> EventResult {
-        // TODO(@MattWindsor91): cursor multiplier
-        EventResult::from_handled(self.cur.move_by(motion, 1) != 0)
+    /// Skips the current split and moves past it, or un-skips it if it was
+    /// already skipped.
+    ///
+    /// Skipping the last split moves past the end of the run.
+    fn skip(&mut self, r: &mut Run) -> EventResult {
+        if r.toggle_skip_at(self.cur.position()) && self.cur.move_down(1) == 0 {
+            return EventResult::Finish;
+        }
+        EventResult::Handled
+    }
+
+    /// Moves the state cursor according to `mv` within `r`, if possible.
+    fn move_cursor(&mut self, mv: cursor::Move, r: &Run) -> EventResult {
+        EventResult::from_handled(self.cur.move_by(mv, &r.group_starts()) != 0)
     }
 
     /// Constructs an editor entering the given field.
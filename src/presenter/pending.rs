@@ -0,0 +1,63 @@
+//! Counts and prefixes typed ahead of the commands they apply to.
+
+use std::fmt::{self, Display};
+
+/// A count and/or prefix typed ahead of a command, as in vi's `5j` or `gg`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Pending {
+    /// The count typed so far, if any.
+    pub count: Option<usize>,
+    /// Whether the first half of a goto (the first `g` of `gg`) was typed.
+    pub goto: bool,
+}
+
+impl Pending {
+    /// Adds `digit` to the end of the count.
+    ///
+    /// Counts can't start with zero, so this returns false (and does nothing)
+    /// if `digit` would be a leading zero.
+    pub fn push_digit(&mut self, digit: u8) -> bool {
+        if digit == 0 && self.count.is_none() {
+            return false;
+        }
+        let count = self.count.unwrap_or(0);
+        self.count = Some(count.saturating_mul(10).saturating_add(usize::from(digit)));
+        true
+    }
+
+    /// Gets whether nothing is pending.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+impl Display for Pending {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(count) = self.count {
+            write!(f, "{count}")?;
+        }
+        if self.goto {
+            f.write_str("g")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests building up a count, ignoring leading zeroes.
+    #[test]
+    fn push_digit() {
+        let mut p = Pending::default();
+        assert!(!p.push_digit(0));
+        assert!(p.is_empty());
+        assert!(p.push_digit(1));
+        assert!(p.push_digit(0));
+        p.goto = true;
+        assert_eq!(p.count, Some(10));
+        assert_eq!(p.to_string(), "10g");
+    }
+}
@@ -0,0 +1,153 @@
+//! The [Picker] mode, used to choose a game and category to run.
+
+use super::{
+    cursor::{self, Cursor},
+    event::Event,
+    mode::{EventResult, Mode},
+    prompt::{self, Hint, Prompt},
+};
+use crate::model::{checkpoint::Checkpoint, practice::Stats, run::Run};
+use thiserror::Error;
+
+/// Something that can be picked: a game or a category.
+#[derive(Clone, Debug)]
+pub struct Choice {
+    /// The ID of the game or category.
+    pub id: String,
+    /// The display name of the game or category.
+    pub name: String,
+}
+
+/// A run loaded from a [Source], ready to time.
+pub struct Loaded {
+    /// The run itself.
+    pub run: Run,
+    /// The checkpoint of an interrupted attempt at the run, if any.
+    pub checkpoint: Option<Checkpoint>,
+}
+
+/// Errors occurring when loading a picked run.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// There is no [Source] to load runs from.
+    #[error("no games to pick from")]
+    NoSource,
+    /// The [Source] failed to load the run.
+    #[error(transparent)]
+    Load(Box<dyn std::error::Error + Send + Sync>),
+}
+
+/// Trait for things that can list games and their categories, and load runs
+/// of them.
+pub trait Source {
+    /// Lists the available games.
+    ///
+    /// This is called whenever the game list is shown, so implementations
+    /// can refresh the list if it may have changed.
+    fn games(&mut self) -> Vec<Choice>;
+
+    /// Lists the categories of the game with ID `game`.
+    fn categories(&mut self, game: &str) -> Vec<Choice>;
+
+    /// Loads a run of the category with ID `category` of the game with ID
+    /// `game`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the game or category can't be found or loaded.
+    fn load(&mut self, game: &str, category: &str) -> Result<Loaded, Error>;
+
+    /// Gets the practice statistics, across every session, for the IL
+    /// category with ID `category` of the game with ID `game`.
+    fn practice_stats(&mut self, game: &str, category: &str) -> Stats;
+}
+
+/// The stage of picking we're at.
+#[derive(Clone, Debug)]
+pub enum Stage {
+    /// Picking a game.
+    Game,
+    /// Picking a category of the given game.
+    Category(Choice),
+}
+
+/// Mode for picking a game, and then one of its categories.
+pub struct Picker {
+    /// The stage of picking we're at.
+    pub stage: Stage,
+    /// The choices at this stage.
+    pub choices: Vec<Choice>,
+    /// The cursor over the choices.
+    cur: Cursor,
+    /// The prompt, which also reports any failure to load the last pick.
+    prompt: String,
+    /// Hints at how to answer the prompt.
+    hints: &'static [Hint],
+}
+
+impl Mode for Picker {
+    fn handle_event(&mut self, e: &Event, _: &mut Run) -> EventResult {
+        match e {
+            Event::Cursor(mv) => self.move_cursor(*mv),
+            _ => EventResult::NotHandled,
+        }
+    }
+
+    fn prompt(&self) -> Option<Prompt<'_>> {
+        Some(Prompt::new(&self.prompt).with_hints(self.hints))
+    }
+
+    fn picker(&self) -> Option<&Picker> {
+        Some(self)
+    }
+}
+
+impl Picker {
+    /// Constructs a picker at stage `stage`, with choices `choices`.
+    #[must_use]
+    pub fn new(stage: Stage, choices: Vec<Choice>) -> Self {
+        let (prompt, hints) = match stage {
+            Stage::Game => ("Pick a game:".to_owned(), prompt::PICK_GAME),
+            Stage::Category(ref game) => (
+                format!("Pick a {} category:", game.name),
+                prompt::PICK_CATEGORY,
+            ),
+        };
+        Self {
+            stage,
+            cur: Cursor::new(choices.len().saturating_sub(1)),
+            choices,
+            prompt,
+            hints,
+        }
+    }
+
+    /// Copies this picker, replacing the prompt with a report that the last
+    /// pick failed with `error`.
+    #[must_use]
+    pub fn with_error(&self, error: &Error) -> Self {
+        Self {
+            stage: self.stage.clone(),
+            choices: self.choices.clone(),
+            cur: self.cur,
+            prompt: format!("Couldn't load: {error}"),
+            hints: &[],
+        }
+    }
+
+    /// Gets the cursor over the choices.
+    #[must_use]
+    pub fn cursor(&self) -> &Cursor {
+        &self.cur
+    }
+
+    /// Gets the choice under the cursor, if there are any choices.
+    #[must_use]
+    pub fn selected(&self) -> Option<&Choice> {
+        self.choices.get(self.cur.position())
+    }
+
+    fn move_cursor(&mut self, mv: cursor::Move) -> EventResult {
+        EventResult::from_handled(self.cur.move_by(mv, &[]) != 0)
+    }
+}
@@ -0,0 +1,91 @@
+//! The [Practice] mode, used to practise single splits and groups.
+
+use super::{
+    cursor::Cursor,
+    editor::Editor,
+    event::Event,
+    mode::{EventResult, Inactive, Mode},
+    nav::Nav,
+    prompt::{self, Prompt},
+};
+use crate::model::{practice::Stats, run::Run};
+
+/// What to practise, relative to the split under the cursor.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Scope {
+    /// The split itself.
+    Split,
+    /// The innermost group containing the split.
+    Group,
+}
+
+/// Mode for practising a single split or group in an individual-level run.
+///
+/// This wraps the usual navigation and editing modes, so that the IL run is
+/// edited just like a full run, but stays in practice until the presenter
+/// ends it.
+pub struct Practice {
+    /// The navigation or editing mode we are wrapping.
+    inner: Box<dyn Mode>,
+    /// The prompt, naming what we are practising.
+    prompt: String,
+}
+
+impl Mode for Practice {
+    fn handle_event(&mut self, e: &Event, run: &mut Run) -> EventResult {
+        match self.inner.handle_event(e, run) {
+            EventResult::Transition(new_mode) => {
+                self.inner.commit(run);
+                self.inner = new_mode;
+                EventResult::Handled
+            }
+            // Practice attempts only end when the presenter ends them.
+            EventResult::Finish => {
+                self.inner.commit(run);
+                self.inner = Box::new(Inactive);
+                EventResult::Handled
+            }
+            result => result,
+        }
+    }
+
+    fn commit(&mut self, run: &mut Run) {
+        self.inner.commit(run);
+    }
+
+    fn cursor(&self) -> Option<&Cursor> {
+        self.inner.cursor()
+    }
+
+    fn editor(&self) -> Option<&Editor> {
+        self.inner.editor()
+    }
+
+    fn prompt(&self) -> Option<Prompt<'_>> {
+        Some(Prompt::new(&self.prompt).with_hints(prompt::PRACTICE))
+    }
+}
+
+impl Practice {
+    /// Constructs a practice mode for the fresh IL run `run`.
+    #[must_use]
+    pub fn new(run: &Run) -> Self {
+        let cur = Cursor::new(run.splits.len().saturating_sub(1));
+        Self {
+            inner: Box::new(Nav::new(cur)),
+            prompt: format!("Practising {}:", run.metadata.category),
+        }
+    }
+}
+
+/// A full run set aside while we practise part of it.
+pub(super) struct SetAside {
+    /// The full run.
+    pub run: Run,
+    /// The cursor to restore when we return to the full run.
+    pub cursor: Cursor,
+    /// The index of the first split being practised in the full run.
+    pub start: usize,
+    /// The statistics for what we are practising, across every session.
+    pub stats: Stats,
+}
@@ -0,0 +1,86 @@
+//! Prompts, and hints at the events that answer them.
+//!
+//! The presenter doesn't know which keys send which events, so prompts carry
+//! the events themselves, and the view names the keys.
+
+use super::{event::Event, practice::Scope};
+
+/// A prompt for the user.
+#[derive(Debug)]
+pub struct Prompt<'a> {
+    /// The text of the prompt.
+    pub text: &'a str,
+    /// Hints at the events the user can send in answer to the prompt.
+    pub hints: &'static [Hint],
+}
+
+impl<'a> Prompt<'a> {
+    /// Constructs a prompt with text `text` and no hints.
+    #[must_use]
+    pub fn new(text: &'a str) -> Self {
+        Self { text, hints: &[] }
+    }
+
+    /// Adds `hints` to this prompt.
+    #[must_use]
+    pub fn with_hints(self, hints: &'static [Hint]) -> Self {
+        Self { hints, ..self }
+    }
+}
+
+/// A hint at an event the user can send, and what it does.
+#[derive(Debug)]
+pub struct Hint {
+    /// The event.
+    pub event: Event,
+    /// A short description of what the event does.
+    pub label: &'static str,
+}
+
+/// Hints for starting a new run.
+pub const NEW_RUN: &[Hint] = &[Hint {
+    event: Event::NewRun,
+    label: "new run",
+}];
+
+/// Hints for resuming an interrupted run.
+pub const RECOVER: &[Hint] = &[
+    Hint {
+        event: Event::Commit,
+        label: "yes",
+    },
+    Hint {
+        event: Event::NewRun,
+        label: "new run",
+    },
+];
+
+/// Hints for practising.
+pub const PRACTICE: &[Hint] = &[
+    Hint {
+        event: Event::NewRun,
+        label: "again",
+    },
+    Hint {
+        event: Event::Practice(Scope::Split),
+        label: "stop",
+    },
+];
+
+/// Hints for picking a game.
+pub const PICK_GAME: &[Hint] = &[Hint {
+    event: Event::Commit,
+    label: "pick",
+}];
+
+/// Hints for picking a category.
+pub const PICK_CATEGORY: &[Hint] = &[
+    Hint {
+        event: Event::Commit,
+        label: "pick",
+    },
+    Hint {
+        event: Event::Undo,
+        label: "back",
+    },
+];
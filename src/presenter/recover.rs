@@ -0,0 +1,69 @@
+//! The [Recover] mode, used to offer up an interrupted run for resumption.
+
+use super::{
+    cursor::Cursor,
+    editor::Editor,
+    event::Event,
+    mode::{EventResult, Mode},
+    nav::Nav,
+    prompt::{self, Prompt},
+};
+use crate::model::{checkpoint::Checkpoint, run::Run};
+
+/// Mode for when we have restored an interrupted run, and are waiting for the
+/// user to confirm that they want to resume it.
+///
+/// This mode shows the cursor and editor the run had when it was interrupted,
+/// and moves into them when the user commits.  Starting a new run instead
+/// abandons the interrupted one.
+pub struct Recover {
+    /// The mode to resume, taken when we transition into it.
+    resume: Option<Box<dyn Mode>>,
+}
+
+impl Mode for Recover {
+    fn handle_event(&mut self, e: &Event, _: &mut Run) -> EventResult {
+        match e {
+            Event::Commit => self.resume(),
+            _ => EventResult::NotHandled,
+        }
+    }
+
+    fn cursor(&self) -> Option<&Cursor> {
+        self.resume.as_ref().and_then(|m| m.cursor())
+    }
+
+    fn editor(&self) -> Option<&Editor> {
+        self.resume.as_ref().and_then(|m| m.editor())
+    }
+
+    fn is_recovering(&self) -> bool {
+        true
+    }
+
+    fn prompt(&self) -> Option<Prompt<'_>> {
+        Some(Prompt::new("Resume run?").with_hints(prompt::RECOVER))
+    }
+}
+
+impl Recover {
+    /// Constructs a recovery mode from the user interface state in
+    /// `checkpoint`, for a run with `num_splits` splits.
+    #[must_use]
+    pub fn new(checkpoint: &Checkpoint, num_splits: usize) -> Self {
+        let cur = Cursor::with_position(checkpoint.cursor, num_splits.saturating_sub(1));
+        let resume: Box<dyn Mode> = match checkpoint.editor {
+            Some(ref e) => Box::new(Editor::restore(cur, e)),
+            None => Box::new(Nav::new(cur)),
+        };
+        Self {
+            resume: Some(resume),
+        }
+    }
+
+    fn resume(&mut self) -> EventResult {
+        self.resume
+            .take()
+            .map_or(EventResult::NotHandled, EventResult::Transition)
+    }
+}
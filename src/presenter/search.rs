@@ -0,0 +1,150 @@
+//! Incremental search for splits by name.
+
+use super::{
+    cursor::Cursor,
+    event::{Edit, Event},
+    mode::{EventResult, Mode},
+};
+use crate::model::run::Run;
+
+/// The direction in which to look for the next match.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Direction {
+    /// Look at splits after the cursor first.
+    Forward,
+    /// Look at splits before the cursor first.
+    Backward,
+}
+
+/// Mode for typing in a search.
+///
+/// As the query changes, the search's own cursor jumps to the first match
+/// after where the search started; the mode we were in before only moves
+/// there if the search is accepted.
+#[derive(Clone, Debug)]
+pub struct Search {
+    /// The query typed so far, not including the leading `/`.
+    query: String,
+    /// The position of the cursor when the search started.
+    origin: usize,
+    /// The cursor, which sits on the current match (or the origin, if there
+    /// is no match).
+    cur: Cursor,
+    /// Whether the current query matches anything.
+    found: bool,
+}
+
+impl Mode for Search {
+    fn handle_event(&mut self, e: &Event, run: &mut Run) -> EventResult {
+        match e {
+            Event::Edit(Edit::Insert(c)) => self.query.push(*c),
+            Event::Edit(Edit::Remove) => {
+                self.query.pop();
+            }
+            _ => return EventResult::NotHandled,
+        }
+        self.update(run);
+        EventResult::Handled
+    }
+
+    fn cursor(&self) -> Option<&Cursor> {
+        Some(&self.cur)
+    }
+
+    fn search(&self) -> Option<&Search> {
+        Some(self)
+    }
+
+    fn takes_text(&self) -> bool {
+        true
+    }
+}
+
+impl Search {
+    /// Starts a search from the cursor `cur`.
+    #[must_use]
+    pub fn new(cur: Cursor) -> Self {
+        Self {
+            query: String::new(),
+            origin: cur.position(),
+            cur,
+            found: false,
+        }
+    }
+
+    /// Gets the query typed so far, not including the leading `/`.
+    #[must_use]
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    /// Gets the split the search has landed on, if the query matches
+    /// anything.
+    #[must_use]
+    pub fn target(&self) -> Option<usize> {
+        Some(self.cur.position()).filter(|_| self.found)
+    }
+
+    /// Gets whether the current query matches anything, or is empty.
+    #[must_use]
+    pub fn is_found(&self) -> bool {
+        self.found || self.query.is_empty()
+    }
+
+    /// Moves the cursor to the first match of the query after the origin.
+    fn update(&mut self, run: &Run) {
+        let target = find(run, &self.query, self.origin, Direction::Forward);
+        self.found = target.is_some();
+        let pos = target.unwrap_or(self.origin);
+        self.cur = Cursor::with_position(pos, run.splits.len().saturating_sub(1));
+    }
+}
+
+/// Gets whether the split name `name` matches `query`.
+///
+/// Matching is by substring, ignoring case; the empty query matches nothing.
+#[must_use]
+pub fn is_match(name: &str, query: &str) -> bool {
+    !query.is_empty() && name.to_lowercase().contains(&query.to_lowercase())
+}
+
+/// Finds the next split in `run` whose name matches `query`, looking in
+/// direction `dir` from the split after (or before) `from`, wrapping around
+/// the ends of the run, and considering `from` itself last.
+#[must_use]
+pub fn find(run: &Run, query: &str, from: usize, dir: Direction) -> Option<usize> {
+    let len = run.splits.len();
+    let step = |n: usize| match dir {
+        Direction::Forward => (from + n) % len,
+        Direction::Backward => (from + len - n % len) % len,
+    };
+    (1..=len)
+        .map(step)
+        .find(|i| is_match(&run.splits[*i].name, query))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::split::Split;
+
+    fn run(names: &[&str]) -> Run {
+        Run {
+            splits: names.iter().map(|n| Split::new(n)).collect(),
+            ..Run::default()
+        }
+    }
+
+    /// Tests finding matches in both directions, wrapping around the run.
+    #[test]
+    fn find_wraps() {
+        let run = run(&["Palmtree Panic 1", "Collision Chaos 1", "Palmtree Panic 2"]);
+        let find = |from, dir| find(&run, "palm", from, dir);
+        assert_eq!(find(0, Direction::Forward), Some(2));
+        assert_eq!(find(2, Direction::Forward), Some(0));
+        assert_eq!(find(1, Direction::Backward), Some(0));
+        assert_eq!(find(0, Direction::Backward), Some(2));
+        assert_eq!(super::find(&run, "tidal", 0, Direction::Forward), None);
+        assert_eq!(super::find(&run, "", 0, Direction::Forward), None);
+    }
+}
@@ -2,7 +2,10 @@
 pub mod config;
 pub mod error;
 mod event;
+pub mod gamepad;
 pub mod gfx;
+pub mod hotkeys;
+pub mod keys;
 
 use crate::presenter;
 use std::cell::RefCell;
@@ -15,50 +18,90 @@ pub struct View {
     sdl: sdl2::Sdl,
     screen: RefCell<sdl2::render::Canvas<sdl2::video::Window>>,
     textures: sdl2::render::TextureCreator<sdl2::video::WindowContext>,
+    ttf: sdl2::ttf::Sdl2TtfContext,
     cfg: Config,
+    keys: keys::Config,
 }
 
 impl View {
-    /// Creates a new view, opening a window in the process.
+    /// Creates a new view with keybindings `keys`, opening a window in the
+    /// process.
     ///
     /// # Errors
     ///
     /// Returns an error if any of the SDL subsystems the UI manager requires
-    /// fail to initialise.
-    pub fn new(cfg: Config) -> Result<Self> {
+    /// fail to initialise, or if `keys` binds a key SDL doesn't know.
+    pub fn new(cfg: Config, keys: keys::Config) -> Result<Self> {
+        if let Some(chord) = keys
+            .map()
+            .chords()
+            .find(|c| sdl2::keyboard::Keycode::from_name(c.key_name()).is_none())
+        {
+            return Err(Error::UnknownKey(chord.clone()));
+        }
         let sdl = sdl2::init().map_err(Error::Init)?;
         let video = sdl.video().map_err(Error::Init)?;
         let window = gfx::make_window(&video, cfg.window)?;
         let screen = window.into_canvas().build().map_err(Error::SdlInteger)?;
         let textures = screen.texture_creator();
+        let ttf = sdl2::ttf::init().map_err(|e| Error::Init(e.to_string()))?;
         Ok(Self {
             sdl,
             screen: RefCell::new(screen),
             textures,
+            ttf,
             cfg,
+            keys,
         })
     }
 
+    /// Sets up game controller handling, with the bindings in `cfg`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SDL can't initialise game controllers, or if
+    /// `cfg` binds a button SDL doesn't know.
+    pub fn gamepads(&self, cfg: &gamepad::Config) -> Result<gamepad::Gamepads> {
+        gamepad::Gamepads::new(&self.sdl, cfg)
+    }
+
     /// Spawns a [Core] handling UI services.
     ///
     /// # Errors
     ///
     /// Returns an error if SDL can't spawn an event pump.
     pub fn spawn(&self, presenter: presenter::Presenter) -> Result<Instance> {
-        let font_manager =
-            gfx::font::Manager::new(&self.textures, &self.cfg.fonts, &self.cfg.colours);
+        let font_manager = gfx::font::Manager::new(
+            &self.textures,
+            &self.ttf,
+            &self.cfg.fonts,
+            &self.cfg.colours,
+        );
         let renderer = gfx::render::Window::new(
             self.screen.borrow_mut(),
             self.cfg.window,
             font_manager,
             &self.cfg.colours,
         )?;
-        let gfx = gfx::Core::new(renderer, self.cfg.window);
+        let gfx = gfx::Core::new(
+            renderer,
+            self.cfg.window,
+            self.cfg.splits,
+            &self.cfg.layout,
+            &self.cfg.extras,
+            &self.keys,
+        );
 
         let events = self.sdl.event_pump().map_err(Error::Init)?;
 
         Ok(Instance {
             events,
+            gamepads: None,
+            hotkeys: None,
+            focused: true,
+            swallow_text: false,
+            keys: &self.keys,
+            initial_size: gfx.size(),
             gfx,
             presenter,
         })
@@ -68,11 +111,42 @@ impl View {
 /// An instance of the view for a particular presenter.
 pub struct Instance<'a> {
     events: sdl2::EventPump,
+    gamepads: Option<gamepad::Gamepads>,
+    hotkeys: Option<hotkeys::Listener>,
+    focused: bool,
+    /// Whether to drop the next text input, as it comes from the key that
+    /// opened a text prompt.
+    swallow_text: bool,
+    keys: &'a keys::Config,
+    /// The size of the window when it was opened.
+    initial_size: (u32, u32),
     gfx: gfx::Core<'a>,
     presenter: presenter::Presenter,
 }
 
 impl<'a> Instance<'a> {
+    /// Takes presenter events from the game controllers in `gamepads`, as
+    /// well as from the keyboard.
+    pub fn set_gamepads(&mut self, gamepads: gamepad::Gamepads) {
+        self.gamepads = Some(gamepads);
+    }
+
+    /// Takes presenter events from global hotkeys heard by `listener`, as
+    /// well as from SDL.
+    ///
+    /// Hotkeys are ignored while the zombiesplit window has focus, as SDL
+    /// will see the same keypresses.
+    pub fn set_hotkeys(&mut self, listener: hotkeys::Listener) {
+        self.hotkeys = Some(listener);
+    }
+
+    /// Gets the size of the window, as width and height, if it has been
+    /// resized since it opened.
+    #[must_use]
+    pub fn resized(&self) -> Option<(u32, u32)> {
+        Some(self.gfx.size()).filter(|s| *s != self.initial_size)
+    }
+
     /// Runs the UI loop.
     ///
     /// # Errors
@@ -84,9 +158,14 @@ impl<'a> Instance<'a> {
         self.gfx.redraw(&self.presenter)?;
 
         while self.presenter.is_running() {
-            for e in self.events.poll_iter() {
-                if let Some(x) = event::from_sdl(&e) {
-                    self.presenter.handle_event(&x)
+            while let Some(e) = self.events.poll_event() {
+                self.handle_sdl(&e);
+            }
+            if let Some(ref hotkeys) = self.hotkeys {
+                for action in hotkeys.actions() {
+                    if !self.focused {
+                        self.presenter.handle_event(&action.event());
+                    }
                 }
             }
             self.gfx.redraw(&self.presenter)?;
@@ -94,4 +173,40 @@ impl<'a> Instance<'a> {
 
         Ok(())
     }
+
+    /// Handles SDL event `e`.
+    fn handle_sdl(&mut self, e: &sdl2::event::Event) {
+        if let Some(focused) = event::focus(e) {
+            self.focused = focused;
+        }
+        if let Some((w, h)) = event::resize(e) {
+            self.gfx.resize(w, h);
+        }
+        if self.presenter.takes_text() {
+            self.handle_text(e);
+        } else if let Some(x) = event::from_sdl(e, self.keys) {
+            self.presenter.handle_event(&x);
+            self.swallow_text = self.presenter.takes_text();
+        }
+        if let Some(ref mut gamepads) = self.gamepads {
+            if let Some(action) = gamepads.handle(e) {
+                self.presenter.handle_event(&action.event());
+            }
+        }
+    }
+
+    /// Handles SDL event `e` while the presenter takes free text.
+    fn handle_text(&mut self, e: &sdl2::event::Event) {
+        match e {
+            sdl2::event::Event::TextInput { .. } if self.swallow_text => {
+                self.swallow_text = false;
+                return;
+            }
+            sdl2::event::Event::KeyDown { .. } => self.swallow_text = false,
+            _ => (),
+        }
+        for x in event::from_sdl_text(e) {
+            self.presenter.handle_event(&x);
+        }
+    }
 }
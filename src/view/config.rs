@@ -3,7 +3,7 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use super::gfx::{colour, font, metrics};
+use super::gfx::{colour, extra, font, layout, metrics, split};
 
 /// Top-level UI configuration.
 #[derive(Serialize, Deserialize, Debug)]
@@ -14,4 +14,13 @@ pub struct Config {
     pub fonts: HashMap<font::Id, font::Config>,
     /// Window metrics.
     pub window: metrics::Window,
+    /// Split viewer configuration.
+    #[serde(default)]
+    pub splits: split::Config,
+    /// The layout of the widgets in the window.
+    #[serde(default)]
+    pub layout: layout::Stack,
+    /// The extra widgets to show between the splits and the total, in order.
+    #[serde(default)]
+    pub extras: Vec<extra::Kind>,
 }
@@ -20,6 +20,14 @@ pub enum Error {
     #[error("SDL windowing error")]
     Window(#[from] sdl2::video::WindowBuildError),
 
+    /// A keybinding names a key SDL doesn't know.
+    #[error("unknown key in keybinding '{0}'")]
+    UnknownKey(super::keys::Chord),
+
+    /// A game controller binding names a button SDL doesn't know.
+    #[error("unknown button in gamepad binding '{0}'")]
+    UnknownButton(super::keys::Chord),
+
     /// An error occurred while building a window.
     #[error("SDL error")]
     SdlInteger(#[from] sdl2::IntegerOrSdlError),
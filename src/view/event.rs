@@ -1,52 +1,73 @@
 //! Mapping from SDL to presenter events.
-use crate::{
-    model::time::position,
-    presenter::{
-        cursor,
-        event::{Edit, Event},
-    },
-};
+use super::keys;
+use crate::presenter::event::{Edit, Event};
 
-/// Maps an event from SDL into [Event].
-pub fn from_sdl(e: &sdl2::event::Event) -> Option<Event> {
+/// Maps an event from SDL into [Event], using the keybindings `keys`.
+pub fn from_sdl(e: &sdl2::event::Event, keys: &keys::Config) -> Option<Event> {
     match e {
         sdl2::event::Event::Quit { .. } => Some(Event::Quit),
         sdl2::event::Event::KeyDown {
-            keycode: Some(k), ..
-        } => from_key(*k),
+            keycode: Some(k),
+            keymod,
+            ..
+        } => keys
+            .map()
+            .action(&keys::Chord::from_sdl(*k, *keymod))
+            .map(keys::Action::event),
         _ => None,
     }
 }
 
-fn from_key(k: sdl2::keyboard::Keycode) -> Option<Event> {
+/// Maps an event from SDL into [Event]s while the presenter takes free text.
+///
+/// Text comes from SDL text input, and only a few editing keys are bound
+/// (these can't be remapped): `Return` commits, `Escape` cancels, `Backspace`
+/// deletes, and `Tab` completes.
+pub fn from_sdl_text(e: &sdl2::event::Event) -> Vec<Event> {
     use sdl2::keyboard::Keycode;
-    match k {
-        // Editing
-        Keycode::Num0 => Some(Event::digit(0)),
-        Keycode::Num1 => Some(Event::digit(1)),
-        Keycode::Num2 => Some(Event::digit(2)),
-        Keycode::Num3 => Some(Event::digit(3)),
-        Keycode::Num4 => Some(Event::digit(4)),
-        Keycode::Num5 => Some(Event::digit(5)),
-        Keycode::Num6 => Some(Event::digit(6)),
-        Keycode::Num7 => Some(Event::digit(7)),
-        Keycode::Num8 => Some(Event::digit(8)),
-        Keycode::Num9 => Some(Event::digit(9)),
-        Keycode::Backspace => Some(Event::Edit(Edit::Remove)),
-        // Time fields
-        // We don't allow entering hours yet, but this may change.
-        Keycode::M => Some(Event::EnterField(position::Name::Minutes)),
-        Keycode::S => Some(Event::EnterField(position::Name::Seconds)),
-        Keycode::Period => Some(Event::EnterField(position::Name::Milliseconds)),
-        // Cursor motions
-        Keycode::J | Keycode::Down | Keycode::Space => Some(Event::Cursor(cursor::Motion::Down)),
-        Keycode::K | Keycode::Up => Some(Event::Cursor(cursor::Motion::Up)),
-        // Top-level commands
-        Keycode::H | Keycode::Left => Some(Event::Undo),
-        Keycode::L | Keycode::Right => Some(Event::Commit),
-        Keycode::X | Keycode::Delete => Some(Event::Delete),
-        Keycode::Return | Keycode::Z => Some(Event::NewRun),
-        Keycode::Escape => Some(Event::Quit),
+    match e {
+        sdl2::event::Event::Quit { .. } => vec![Event::Quit],
+        sdl2::event::Event::TextInput { text, .. } => {
+            text.chars().map(|c| Event::Edit(Edit::Insert(c))).collect()
+        }
+        sdl2::event::Event::KeyDown {
+            keycode: Some(k), ..
+        } => match k {
+            Keycode::Return | Keycode::KpEnter => vec![Event::Commit],
+            Keycode::Escape => vec![Event::Undo],
+            Keycode::Backspace => vec![Event::Edit(Edit::Remove)],
+            Keycode::Tab => vec![Event::Complete],
+            _ => vec![],
+        },
+        _ => vec![],
+    }
+}
+
+/// Gets the new width and height of the window if SDL event `e` resizes it.
+pub fn resize(e: &sdl2::event::Event) -> Option<(u32, u32)> {
+    use std::convert::TryFrom;
+    match e {
+        sdl2::event::Event::Window {
+            win_event: sdl2::event::WindowEvent::SizeChanged(w, h),
+            ..
+        } => Some((u32::try_from(*w).ok()?, u32::try_from(*h).ok()?)),
+        _ => None,
+    }
+}
+
+/// Gets whether SDL event `e` gains (`Some(true)`) or loses
+/// (`Some(false)`) the window keyboard focus.
+pub fn focus(e: &sdl2::event::Event) -> Option<bool> {
+    use sdl2::event::WindowEvent;
+    match e {
+        sdl2::event::Event::Window {
+            win_event: WindowEvent::FocusGained,
+            ..
+        } => Some(true),
+        sdl2::event::Event::Window {
+            win_event: WindowEvent::FocusLost,
+            ..
+        } => Some(false),
         _ => None,
     }
 }
@@ -0,0 +1,134 @@
+//! Game controller input.
+//!
+//! Game controllers (including anything else SDL recognises as one, such as
+//! some foot pedals) are an extra source of events alongside the keyboard.
+//! Controllers are opened as SDL reports them plugged in, and buttons on any
+//! of them map to actions through the `[gamepad]` section of the system
+//! config.
+
+use super::{
+    error::{Error, Result},
+    keys::{self, Action, Bindings, Chord, Modifiers},
+};
+use sdl2::controller::{Button, GameController};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, convert::TryFrom};
+
+/// Game controller configuration.
+///
+/// This maps action names to lists of SDL button names (for instance, `a`,
+/// `start`, or `dpdown`), and there are no default bindings.  Like keyboard
+/// bindings, it is checked for conflicts when it is loaded; buttons can't
+/// have modifiers.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(try_from = "Bindings", into = "Bindings")]
+pub struct Config {
+    /// The bindings as configured.
+    bindings: Bindings,
+    /// The action bound to each button.
+    map: keys::Map,
+}
+
+impl TryFrom<Bindings> for Config {
+    type Error = keys::Error;
+
+    fn try_from(bindings: Bindings) -> keys::Result<Self> {
+        if let Some(chord) = bindings
+            .values()
+            .flatten()
+            .find(|c| c.modifiers() != Modifiers::default())
+        {
+            return Err(keys::Error::ModifiedButton(chord.clone()));
+        }
+        let map = keys::Map::new(&bindings)?;
+        Ok(Self { bindings, map })
+    }
+}
+
+impl From<Config> for Bindings {
+    fn from(c: Config) -> Self {
+        c.bindings
+    }
+}
+
+/// The game controllers currently plugged in, and the bindings for them.
+pub struct Gamepads {
+    /// The SDL game controller subsystem.
+    subsystem: sdl2::GameControllerSubsystem,
+    /// The open controllers, by joystick instance ID.
+    controllers: HashMap<u32, GameController>,
+    /// The action bound to each button.
+    map: keys::Map,
+}
+
+impl Gamepads {
+    /// Sets up game controller handling for `sdl`, using the bindings in
+    /// `cfg`.
+    ///
+    /// Controllers keep sending events while the zombiesplit window is in
+    /// the background, so they work while the game has focus.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if SDL can't initialise its game controller
+    /// subsystem, or if `cfg` binds a button SDL doesn't know.
+    pub fn new(sdl: &sdl2::Sdl, cfg: &Config) -> Result<Self> {
+        if let Some(chord) = cfg
+            .map
+            .chords()
+            .find(|c| Button::from_string(c.key_name()).is_none())
+        {
+            return Err(Error::UnknownButton(chord.clone()));
+        }
+        // This must be set before the subsystem starts.
+        sdl2::hint::set("SDL_JOYSTICK_ALLOW_BACKGROUND_EVENTS", "1");
+        Ok(Self {
+            subsystem: sdl.game_controller().map_err(Error::Init)?,
+            controllers: HashMap::new(),
+            map: cfg.map.clone(),
+        })
+    }
+
+    /// Handles SDL event `e`, opening and closing controllers as they come
+    /// and go, and returning the action bound to any button pressed.
+    pub fn handle(&mut self, e: &sdl2::event::Event) -> Option<Action> {
+        use sdl2::event::Event;
+        match e {
+            Event::ControllerDeviceAdded { which, .. } => {
+                // A controller we can't open is no use to us, but shouldn't
+                // stop the timer.
+                if let Ok(c) = self.subsystem.open(*which) {
+                    self.controllers.insert(c.instance_id(), c);
+                }
+                None
+            }
+            Event::ControllerDeviceRemoved { which, .. } => {
+                self.controllers.remove(which);
+                None
+            }
+            Event::ControllerButtonDown { button, .. } => {
+                self.map.action(&Chord::key(&button.string()))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that buttons with modifiers are rejected.
+    #[test]
+    fn modified_buttons() {
+        let cfg: Config = toml::from_str(r#"down = ["a", "dpdown"]"#).unwrap();
+        assert_eq!(cfg.map.action(&Chord::key("a")), Some(Action::Down));
+
+        let err = toml::from_str::<Config>(r#"down = ["shift+a"]"#).unwrap_err();
+        assert!(
+            err.to_string().contains("buttons can't have modifiers"),
+            "unexpected error: {}",
+            err
+        );
+    }
+}
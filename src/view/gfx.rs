@@ -1,37 +1,88 @@
 //! Graphics rendering.
 
 pub mod colour;
+mod command;
 mod editor;
+pub mod extra;
+mod finish;
+mod flash;
 pub mod font;
 mod header;
+pub mod layout;
 pub mod metrics; // for now
+mod pick;
 mod position;
 pub mod render;
-mod split;
+pub mod split;
+mod status;
 mod total;
 mod widget;
 
 use crate::presenter::Presenter;
 
-use super::error::{Error, Result};
+use super::{
+    error::{Error, Result},
+    keys,
+};
 
 use widget::Widget;
 
 pub struct Core<'a> {
     renderer: render::Window<'a>,
-    widgets: Vec<Box<dyn widget::Widget>>,
+    widgets: Vec<Box<dyn widget::Widget + 'a>>,
+    /// The current window metrics.
+    wmetrics: metrics::Window,
+    /// The split viewer configuration, kept for laying out the widgets again.
+    split_cfg: split::Config,
+    /// The layout of the widgets.
+    layout: &'a layout::Stack,
+    /// The extra widgets to show.
+    extras: &'a [extra::Kind],
+    /// The keybindings, used to hint at which keys answer prompts.
+    keys: &'a keys::Config,
 }
 
 impl<'a> Core<'a> {
     /// Creates a new graphics core.
     #[must_use]
-    pub fn new(renderer: render::Window<'a>, wmetrics: metrics::Window) -> Self {
+    pub fn new(
+        renderer: render::Window<'a>,
+        wmetrics: metrics::Window,
+        split_cfg: split::Config,
+        layout: &'a layout::Stack,
+        extras: &'a [extra::Kind],
+        keys: &'a keys::Config,
+    ) -> Self {
         Self {
             renderer,
-            widgets: make_widgets(wmetrics),
+            widgets: make_widgets(wmetrics, split_cfg, layout, extras, keys),
+            wmetrics,
+            split_cfg,
+            layout,
+            extras,
+            keys,
         }
     }
 
+    /// Gets the current size of the window, as width and height.
+    #[must_use]
+    pub fn size(&self) -> (u32, u32) {
+        (self.wmetrics.win_w, self.wmetrics.win_h)
+    }
+
+    /// Lays the widgets out again for a window of size `w` by `h`.
+    pub fn resize(&mut self, w: u32, h: u32) {
+        self.wmetrics = self.wmetrics.resized(w, h);
+        self.renderer.set_metrics(self.wmetrics);
+        self.widgets = make_widgets(
+            self.wmetrics,
+            self.split_cfg,
+            self.layout,
+            self.extras,
+            self.keys,
+        );
+    }
+
     /// Redraws the user interface.
     ///
     /// # Errors
@@ -50,39 +101,58 @@ impl<'a> Core<'a> {
     }
 }
 
-fn make_widgets(wmetrics: metrics::Window) -> Vec<Box<dyn Widget>> {
-    vec![
-        make_splits(wmetrics),
-        make_header(wmetrics),
-        make_editor(wmetrics),
-        make_total(wmetrics),
-    ]
-}
-
-fn make_splits(wmetrics: metrics::Window) -> Box<dyn Widget> {
-    Box::new(split::Widget::new(
-        wmetrics.splits_rect(),
-        metrics::sat_i32(wmetrics.split_h),
-    ))
-}
-
-fn make_header(wmetrics: metrics::Window) -> Box<dyn Widget> {
-    Box::new(header::Widget {
-        rect: wmetrics.header_rect(),
-    })
-}
-
-fn make_editor(wmetrics: metrics::Window) -> Box<dyn Widget> {
-    Box::new(editor::Widget::new(wmetrics.editor_rect()))
+/// Makes the widgets in `layout`, in layout order, hinting at the keys in
+/// `keys` when prompting.
+fn make_widgets<'a>(
+    wmetrics: metrics::Window,
+    split_cfg: split::Config,
+    layout: &layout::Stack,
+    extras: &[extra::Kind],
+    keys: &'a keys::Config,
+) -> Vec<Box<dyn Widget + 'a>> {
+    let mut widgets: Vec<Box<dyn Widget + 'a>> = Vec::new();
+    for layout::Placed { kind, rect, font } in layout.lay_out(&wmetrics, extras.len()) {
+        match kind {
+            layout::Kind::Header => widgets.push(Box::new(header::Widget { rect, font })),
+            layout::Kind::Splits => widgets.push(Box::new(split::Widget::new(
+                rect,
+                metrics::sat_i32(wmetrics.split_h),
+                split_cfg,
+                font,
+            ))),
+            layout::Kind::Extras => widgets.extend(make_extras(rect, font, wmetrics, extras)),
+            layout::Kind::Total => widgets.push(Box::new(total::Widget { rect, font })),
+            layout::Kind::Status => {
+                widgets.push(Box::new(status::Widget { rect, font, keys }));
+                widgets.push(Box::new(command::Widget { rect, font }));
+            }
+        }
+    }
+    widgets
 }
 
-fn make_total(wmetrics: metrics::Window) -> Box<dyn Widget> {
-    Box::new(total::Widget {
-        rect: wmetrics.total_rect(),
-    })
+/// Makes the extra widgets `extras` in `rect`, one row each, in order, with
+/// their values in font `font`.
+fn make_extras(
+    mut rect: metrics::Rect,
+    font: font::Id,
+    wmetrics: metrics::Window,
+    extras: &[extra::Kind],
+) -> Vec<Box<dyn Widget>> {
+    rect.h = wmetrics.split_h;
+    let mut widgets: Vec<Box<dyn Widget>> = Vec::with_capacity(extras.len());
+    for kind in extras {
+        widgets.push(Box::new(extra::Widget {
+            rect,
+            kind: *kind,
+            font,
+        }));
+        rect.y += metrics::sat_i32(wmetrics.split_h);
+    }
+    widgets
 }
 
-/// Makes a zombiesplit window.
+/// Makes a resizable zombiesplit window.
 ///
 /// # Errors
 ///
@@ -91,10 +161,16 @@ pub fn make_window(
     video: &sdl2::VideoSubsystem,
     wmetrics: metrics::Window,
 ) -> Result<sdl2::video::Window> {
-    let window = video
-        .window("zombiesplit", wmetrics.win_w, wmetrics.win_h)
-        .position_centered()
-        .build()
-        .map_err(Error::Window)?;
+    let mut builder = video.window("zombiesplit", wmetrics.win_w, wmetrics.win_h);
+    builder.position_centered().resizable();
+    if wmetrics.borderless {
+        builder.borderless();
+    }
+    if wmetrics.always_on_top {
+        // The SDL bindings don't have a builder method for this flag.
+        let on_top = sdl2::sys::SDL_WindowFlags::SDL_WINDOW_ALWAYS_ON_TOP as u32;
+        builder.set_window_flags(builder.window_flags() | on_top);
+    }
+    let window = builder.build().map_err(Error::Window)?;
     Ok(window)
 }
@@ -66,6 +66,9 @@ pub struct Set {
     /// Foreground text for the split currently under the cursor.
     pub fg_cursor: Colour,
 
+    /// Foreground text for splits matching the current search.
+    pub fg_search_match: Colour,
+
     /// Foreground text for a time when there is no time entered.
     pub fg_time_none: Colour,
 
@@ -91,6 +94,8 @@ pub enum Key {
     Header,
     /// Maps to the colour of a split name at a given position.
     Name(SplitPosition),
+    /// Maps to the colour of a split name matching the current search.
+    SearchMatch,
     /// Maps to a time that hasn't been reported.
     NoTime,
     /// Maps to a pacing colour.
@@ -104,6 +109,7 @@ impl Set {
         match key {
             Key::Header => self.fg_header,
             Key::Name(pos) => self.by_split_position(pos),
+            Key::SearchMatch => self.fg_search_match,
             Key::NoTime => self.fg_time_none,
             Key::Pace(pace) => self.by_pace(pace),
             Key::Editor => self.fg_editor,
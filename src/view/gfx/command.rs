@@ -0,0 +1,51 @@
+//! The command line widget, which also shows searches being typed.
+
+use super::{
+    colour, font, metrics,
+    position::Position,
+    render::{Region, Renderer},
+    widget,
+};
+use crate::{presenter::Presenter, view::error::Result};
+
+/// Views the command line, or the search being typed, when the presenter is
+/// at either.
+///
+/// This shares the status line's space, which is otherwise empty while
+/// typing a command.
+pub struct Widget {
+    /// The bounding box for the command line widget.
+    pub rect: metrics::Rect,
+    /// The font for the widget's text.
+    pub font: font::Id,
+}
+
+impl widget::Widget for Widget {
+    fn render(&mut self, r: &mut dyn Renderer, p: &Presenter) -> Result<()> {
+        if let Some(search) = p.search() {
+            let mut r = Region::new(r, self.rect);
+            r.set_pos(Position::top_left(0, 0));
+            r.set_font(self.font)?;
+            // Searches that match nothing are shown as errors.
+            r.set_fg_colour(if search.is_found() {
+                colour::Key::Editor
+            } else {
+                colour::Key::Header
+            });
+            r.put_str(&format!("/{}_", search.query()))?;
+        }
+        if let Some(line) = p.command_line() {
+            let mut r = Region::new(r, self.rect);
+            r.set_pos(Position::top_left(0, 0));
+            r.set_font(self.font)?;
+            if let Some(error) = line.error() {
+                r.set_fg_colour(colour::Key::Header);
+                r.put_str(&format!("E: {error}"))?;
+            } else {
+                r.set_fg_colour(colour::Key::Editor);
+                r.put_str(&format!(":{}_", line.input()))?;
+            }
+        }
+        Ok(())
+    }
+}
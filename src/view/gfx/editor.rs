@@ -1,81 +1,63 @@
 //! Logic for drawing the split editor.
 
 use super::{
-    colour, font, metrics,
+    colour, font,
     position::{Position, X, Y},
-    render::{Region, Renderer},
-    split, widget,
+    render::Renderer,
+    split,
 };
 use crate::{
     model::time,
-    presenter::{
-        editor::{Editor, Field},
-        Presenter,
-    },
+    presenter::editor::{Editor, Field},
     view::error::Result,
 };
 
-/// The editor widget.
-pub struct Widget {
-    /// The bounding box for the header widget, to be offset by the split
-    /// position.
-    rect: metrics::Rect,
-}
-
-impl widget::Widget for Widget {
-    fn render(&mut self, r: &mut dyn Renderer, p: &Presenter) -> Result<()> {
-        if let Some(e) = p.editor() {
-            let mut rect = self.rect;
-            rect.y += metrics::sat_i32(rect.h) * metrics::sat_i32(e.cur.position());
-            let mut r = Region::new(r, rect);
-            draw_editor(&mut r, e)?;
-        }
-        Ok(())
-    }
-}
-
-impl Widget {
-    /// Creates a new editor widget given the baseline rect.
-    pub fn new(rect: metrics::Rect) -> Self {
-        Self { rect }
-    }
-}
-
-/// Draws any editor required by the current state.
-fn draw_editor(r: &mut dyn Renderer, editor: &Editor) -> Result<()> {
-    // Every part of the editor uses the normal font.
-    r.set_font(font::Id::Normal)?;
+/// Draws `editor` onto the row of the split it is editing, represented by `r`,
+/// in font `font`.
+///
+/// The editor replaces the split's time.
+pub(super) fn draw(r: &mut dyn Renderer, editor: &Editor, font: font::Id) -> Result<()> {
+    // Every part of the editor uses the same font, so that it lines up.
+    r.set_font(font)?;
 
-    draw_time(r, editor)?;
+    let time = split::time_str(editor.time);
+    draw_time(r, &time)?;
     if let Some(ref f) = editor.field {
-        draw_field(r, f)?;
+        draw_field(r, f, &time)?;
     };
     Ok(())
 }
 
-fn draw_time(r: &mut dyn Renderer, editor: &Editor) -> Result<()> {
-    move_to_editor(r);
+fn draw_time(r: &mut dyn Renderer, time: &str) -> Result<()> {
+    move_to_editor(r, time);
     r.set_fg_colour(colour::Key::Editor);
-    r.put_str(&split::time_str(editor.time))
+    r.put_str(time)
 }
 
-fn move_to_editor(r: &mut dyn Renderer) {
+fn move_to_editor(r: &mut dyn Renderer, time: &str) {
     // TODO(@MattWindsor91): fix editor position.
     r.set_pos(Position {
         x: X::Right(0),
         y: Y::Top(0),
     });
-    r.move_chars(-metrics::TIME_CHARS, 0);
+    let w = r.str_w(time);
+    r.set_pos(Position::rel(-w, 0));
 }
 
-fn draw_field(r: &mut dyn Renderer, field: &Field) -> Result<()> {
-    // Position floats above main editor.
-    r.move_chars(field_char_offset(field.position()), 0);
+fn draw_field(r: &mut dyn Renderer, field: &Field, time: &str) -> Result<()> {
+    // Position floats above main editor, measuring the time up to the field
+    // so that this works with proportional fonts.
+    let before: String = time
+        .chars()
+        .take(field_char_offset(field.position()))
+        .collect();
+    let w = r.str_w(&before);
+    r.set_pos(Position::rel(w, 0));
     r.set_fg_colour(colour::Key::FieldEditor);
     r.put_str(&field.to_string())
 }
 
-fn field_char_offset(field: time::position::Name) -> i32 {
+fn field_char_offset(field: time::position::Name) -> usize {
     match field {
         // Hours not supported yet.
         time::position::Name::Hours | time::position::Name::Minutes => 0,
@@ -0,0 +1,108 @@
+//! Extra one-line widgets, modelled on the components of `LiveSplit`, that
+//! can be shown between the splits and the total.
+
+use serde::{Deserialize, Serialize};
+
+use super::{
+    colour, font, metrics,
+    position::{Position, X},
+    render::{Region, Renderer},
+    split::{delta_str, time_str},
+    widget,
+};
+use crate::{
+    model::{pace::Delta, time::Time},
+    presenter::{cursor::Cursor, Presenter},
+    view::error::Result,
+};
+
+/// The kinds of extra widget.
+///
+/// The `extras` list in the UI configuration picks which of these to show,
+/// and in which order.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Kind {
+    /// How the split before the cursor did against the comparison.
+    PreviousSegment,
+    /// The projected final time of the run.
+    CurrentPace,
+    /// How much time could be saved on the split under the cursor.
+    PossibleTimeSave,
+    /// The sum of the best times of every split.
+    SumOfBest,
+    /// The attempt number, and the number of attempts this session.
+    Attempts,
+}
+
+impl Kind {
+    /// Gets the label shown on the left of the widget.
+    fn label(self) -> &'static str {
+        match self {
+            Self::PreviousSegment => "Previous segment",
+            Self::CurrentPace => "Current pace",
+            Self::PossibleTimeSave => "Possible time save",
+            Self::SumOfBest => "Sum of best",
+            Self::Attempts => "Attempts",
+        }
+    }
+
+    /// Works out the value shown on the right of the widget, and its colour.
+    fn value(self, p: &Presenter) -> (String, colour::Key) {
+        let cursor = p.mode.cursor().map(Cursor::position);
+        match self {
+            Self::PreviousSegment => cursor
+                .and_then(|c| c.checked_sub(1))
+                .and_then(|c| p.run.segment_delta(c))
+                .map_or_else(none, delta),
+            Self::CurrentPace => time(p.run.projected_total()),
+            Self::PossibleTimeSave => time(cursor.and_then(|c| p.run.possible_time_save(c))),
+            Self::SumOfBest => time(p.run.sum_of_best()),
+            Self::Attempts => (
+                format!("{} ({} this session)", p.run.attempt, p.session.attempts),
+                colour::Key::Header,
+            ),
+        }
+    }
+}
+
+/// Shows a time, or a placeholder if there isn't one.
+fn time(time: Option<Time>) -> (String, colour::Key) {
+    time.map_or_else(none, |t| (time_str(t), colour::Key::Header))
+}
+
+/// Shows a delta, coloured by whether it is ahead or behind.
+fn delta(delta: Delta) -> (String, colour::Key) {
+    (delta_str(delta), colour::Key::Pace(delta.pace()))
+}
+
+/// Shows that there is nothing to show.
+fn none() -> (String, colour::Key) {
+    ("-".to_owned(), colour::Key::NoTime)
+}
+
+/// An extra widget.
+pub struct Widget {
+    /// The bounding box for the widget.
+    pub rect: metrics::Rect,
+    /// Which extra widget this is.
+    pub kind: Kind,
+    /// The font for the value.
+    pub font: font::Id,
+}
+
+impl widget::Widget for Widget {
+    fn render(&mut self, r: &mut dyn Renderer, p: &Presenter) -> Result<()> {
+        let mut r = Region::new(r, self.rect);
+        r.set_pos(Position::top_left(0, 0));
+        r.set_font(font::Id::Normal)?;
+        r.set_fg_colour(colour::Key::Header);
+        r.put_str(self.kind.label())?;
+
+        let (value, colour) = self.kind.value(p);
+        r.set_pos(Position::x(X::Right(0)));
+        r.set_font(self.font)?;
+        r.set_fg_colour(colour);
+        r.put_str_r(&value)
+    }
+}
@@ -0,0 +1,99 @@
+//! Drawing the summary of a finished run.
+
+use super::{
+    colour, font,
+    position::{Position, X},
+    render::Renderer,
+    split::{delta_str, time_str},
+};
+use crate::{
+    model::{pace::Pace, run::Outcome, run::Run},
+    presenter::finish::Summary,
+    view::error::Result,
+};
+
+/// A row of a run summary.
+pub(super) struct Row {
+    /// The label on the left of the row.
+    label: String,
+    /// The value on the right of the row.
+    value: String,
+    /// The colour of the value.
+    colour: colour::Key,
+}
+
+impl Row {
+    fn new(label: impl Into<String>, value: impl Into<String>, colour: colour::Key) -> Self {
+        Self {
+            label: label.into(),
+            value: value.into(),
+            colour,
+        }
+    }
+}
+
+/// Lays out the summary `summary` of the run `run` as rows.
+///
+/// A new personal best shows in its colour unless `dim`, so that it can blink.
+pub(super) fn rows(summary: &Summary, run: &Run, dim: bool) -> Vec<Row> {
+    let outcome = match summary.outcome {
+        Outcome::Completed => "Run complete".to_owned(),
+        Outcome::Reset(split) => run.splits.get(split).map_or_else(
+            || "Run over".to_owned(),
+            |s| format!("Run over at {}", s.name),
+        ),
+    };
+    let total_pace = if summary.is_pb() && !dim {
+        Pace::PersonalBest
+    } else {
+        Pace::Inconclusive
+    };
+    let pb = match (summary.pb_delta(), summary.is_pb()) {
+        (Some(delta), _) => Row::new(
+            "Against PB",
+            delta_str(delta),
+            colour::Key::Pace(delta.pace()),
+        ),
+        (None, true) => Row::new(
+            "Against PB",
+            "first PB",
+            colour::Key::Pace(Pace::PersonalBest),
+        ),
+        (None, false) => Row::new("Against PB", "-", colour::Key::NoTime),
+    };
+    let session_best = summary
+        .session_best
+        .map_or_else(|| "-".to_owned(), time_str);
+
+    vec![
+        Row::new(
+            outcome,
+            time_str(summary.total),
+            colour::Key::Pace(total_pace),
+        ),
+        pb,
+        Row::new(
+            "Gold splits",
+            summary.golds.to_string(),
+            colour::Key::Header,
+        ),
+        Row::new(
+            "Session",
+            format!("{}/{} completed", summary.completions, summary.attempts),
+            colour::Key::Header,
+        ),
+        Row::new("Session best", session_best, colour::Key::Header),
+    ]
+}
+
+/// Draws `row`, with its label on the left and its value on the right, in
+/// font `font`.
+pub(super) fn draw_row(r: &mut dyn Renderer, row: &Row, font: font::Id) -> Result<()> {
+    r.set_font(font)?;
+    r.set_fg_colour(colour::Key::Header);
+    r.put_str(&row.label)?;
+
+    r.set_pos(Position::x(X::Right(0)));
+    r.set_fg_colour(row.colour);
+    r.put_str_r(&row.value)
+}
@@ -0,0 +1,27 @@
+//! Blinking for celebrations.
+
+use crate::presenter::{
+    flash::{Flash, Target},
+    Presenter,
+};
+
+/// How long each phase of a flash's blinking lasts, in milliseconds.
+const BLINK_MS: u128 = 250;
+
+/// Gets whether anything celebrated as `target` should be drawn lit up right
+/// now: that is, whether `p` is celebrating `target`, and the flash is in an
+/// 'on' phase of its blinking.
+pub(super) fn is_lit(p: &Presenter, target: Target) -> bool {
+    p.flash().is_some_and(|f| f.target == target && is_on(f))
+}
+
+/// Gets whether anything celebrated as `target` should be drawn dimmed right
+/// now: that is, whether `p` is celebrating `target`, and the flash is in an
+/// 'off' phase of its blinking.
+pub(super) fn is_dimmed(p: &Presenter, target: Target) -> bool {
+    p.flash().is_some_and(|f| f.target == target && !is_on(f))
+}
+
+fn is_on(flash: &Flash) -> bool {
+    (flash.age().as_millis() / BLINK_MS).is_multiple_of(2)
+}
@@ -4,72 +4,188 @@
 
 use serde::{Deserialize, Serialize};
 use serde_with::{DeserializeFromStr, SerializeDisplay};
-use std::{collections::HashMap, fmt::Display, rc::Rc, str::FromStr};
+use std::{collections::HashMap, convert::TryFrom, fmt::Display, rc::Rc, str::FromStr};
 use thiserror::Error;
 
 use sdl2::{
     image::LoadTexture,
+    pixels::Color,
+    rect::Rect,
     render::{Texture, TextureCreator},
+    ttf,
     video::WindowContext,
 };
 
 use super::{colour, metrics};
 
+/// The character drawn in place of any character a font doesn't have.
+const REPLACEMENT: char = '?';
+
 /// A font manager, using a SDL texture creator.
 pub struct Manager<'a> {
     /// The texture creator used to load fonts.
     creator: &'a TextureCreator<WindowContext>,
-    /// The map of current font textures.
+    /// The TrueType context used to load TrueType fonts.
+    ttf: &'a ttf::Sdl2TtfContext,
+    /// The map of current bitmap font textures.
     textures: HashMap<(Id, colour::Key), Rc<Texture<'a>>>,
+    /// The map of loaded TrueType font faces.
+    faces: HashMap<Id, ttf::Font<'a, 'static>>,
+    /// The cache of rendered TrueType glyphs.
+    ///
+    /// Each font ID has one size, so this is in effect keyed by size.
+    glyphs: HashMap<(Id, colour::Key, char), Glyph<'a>>,
     /// The map of known font configurations.
     configs: &'a HashMap<Id, Config>,
     /// The colour set, used for setting up font colours.
     colour_set: &'a colour::Set,
 }
 
+/// A glyph, ready to be copied onto the screen.
+#[derive(Clone)]
+pub struct Glyph<'a> {
+    /// The texture holding the glyph.
+    pub texture: Rc<Texture<'a>>,
+    /// The part of the texture holding the glyph.
+    pub src: Rect,
+    /// The width of the glyph on screen, in pixels.
+    pub w: u32,
+    /// The height of the glyph on screen, in pixels.
+    pub h: u32,
+    /// How far to move right after drawing the glyph, in pixels.
+    pub advance: i32,
+}
+
 impl<'a> Manager<'a> {
-    /// Creates a font manager with the given texture creator and config hashmap.
+    /// Creates a font manager with the given texture creator, TrueType
+    /// context, and config hashmap.
     #[must_use]
     pub fn new(
         creator: &'a TextureCreator<WindowContext>,
+        ttf: &'a ttf::Sdl2TtfContext,
         configs: &'a HashMap<Id, Config>,
         colour_set: &'a colour::Set,
     ) -> Self {
         Self {
             creator,
+            ttf,
             textures: HashMap::new(),
+            faces: HashMap::new(),
+            glyphs: HashMap::new(),
             configs,
             colour_set,
         }
     }
 
-    /// Gets the given font (with the given colour) as a texture, or loads it if
-    /// it hasn't yet been loaded.
+    /// Gets the glyph for `char` in the given font (with the given colour),
+    /// loading the font and rendering the glyph if needed.
+    ///
+    /// Characters the font doesn't have come out as question marks.
     ///
     /// # Errors
     ///
-    /// Returns an error if we need to load the font but SDL cannot for some
-    /// reason, or the font is not configured.
-    pub fn texture(&mut self, id: Id, colour: colour::Key) -> Result<Rc<Texture<'a>>> {
-        self.textures
-            .get(&(id, colour))
-            .cloned()
-            .map_or_else(|| self.cache(id, colour), Ok)
+    /// Returns an error if we need to load the font or render the glyph but
+    /// SDL cannot for some reason, or the font is not configured.
+    pub fn glyph(&mut self, id: Id, colour: colour::Key, char: char) -> Result<Glyph<'a>> {
+        let id = self.resolve(id);
+        match self.config(id)? {
+            Config::Bitmap { metrics, scale, .. } => {
+                let (metrics, scaled) = (*metrics, metrics.scaled(*scale));
+                let byte = u8::try_from(u32::from(char)).unwrap_or(REPLACEMENT as u8);
+                Ok(Glyph {
+                    texture: self.texture(id, colour)?,
+                    src: Rect::new(
+                        metrics.glyph_x(byte),
+                        metrics.glyph_y(byte),
+                        u32::from(metrics.char_w),
+                        u32::from(metrics.char_h),
+                    ),
+                    w: u32::from(scaled.char_w),
+                    h: u32::from(scaled.char_h),
+                    advance: i32::from(scaled.padded_w()),
+                })
+            }
+            Config::TrueType { .. } => {
+                if let Some(glyph) = self.glyphs.get(&(id, colour, char)) {
+                    return Ok(glyph.clone());
+                }
+                let glyph = self.render(id, colour, char)?;
+                self.glyphs.insert((id, colour, char), glyph.clone());
+                Ok(glyph)
+            }
+        }
     }
 
     /// Gets the given font's metrics set.
     ///
+    /// For TrueType fonts, which have no fixed character width, the width is
+    /// that of a digit.
+    ///
     /// # Errors
     ///
-    /// Returns an error if the font is not configured.
-    pub fn metrics(&self, id: Id) -> Result<metrics::Font> {
-        self.config(id).map(|x| x.metrics)
+    /// Returns an error if the font is not configured, or is a TrueType font
+    /// that SDL can't load.
+    pub fn metrics(&mut self, id: Id) -> Result<metrics::Font> {
+        let id = self.resolve(id);
+        match self.config(id)? {
+            Config::Bitmap { metrics, scale, .. } => Ok(metrics.scaled(*scale)),
+            Config::TrueType { .. } => {
+                let face = self.face(id)?;
+                let char_w = face.find_glyph_metrics('0').map_or(0, |m| m.advance);
+                Ok(metrics::Font {
+                    cols: 16,
+                    char_w: u8::try_from(char_w).unwrap_or(u8::MAX),
+                    char_h: u8::try_from(face.height()).unwrap_or(u8::MAX),
+                    pad_w: 0,
+                    pad_h: 0,
+                })
+            }
+        }
+    }
+
+    /// Measures the width of `str`, in pixels, in the given font.
+    ///
+    /// TrueType fonts are measured glyph by glyph, and so are proportional;
+    /// they must have been loaded already, by asking for their metrics.
+    #[must_use]
+    pub fn str_w(&self, id: Id, str: &str) -> i32 {
+        let id = self.resolve(id);
+        match (self.configs.get(&id), self.faces.get(&id)) {
+            (Some(Config::Bitmap { metrics, scale, .. }), _) => metrics
+                .scaled(*scale)
+                .span_w(metrics::sat_i32(str.chars().count())),
+            (_, Some(face)) => str
+                .chars()
+                .map(|c| {
+                    face.find_glyph_metrics(provided(face, c))
+                        .map_or(0, |m| m.advance)
+                })
+                .sum(),
+            _ => 0,
+        }
+    }
+
+    /// Gets the font to use for `id`, which is the normal font if `id`
+    /// isn't configured.
+    fn resolve(&self, id: Id) -> Id {
+        if self.configs.contains_key(&id) {
+            id
+        } else {
+            Id::Normal
+        }
     }
 
-    fn config(&self, id: Id) -> Result<&Config> {
+    fn config(&self, id: Id) -> Result<&'a Config> {
         self.configs.get(&id).ok_or(Error::Config(id))
     }
 
+    fn texture(&mut self, id: Id, colour: colour::Key) -> Result<Rc<Texture<'a>>> {
+        self.textures
+            .get(&(id, colour))
+            .cloned()
+            .map_or_else(|| self.cache(id, colour), Ok)
+    }
+
     fn cache(&mut self, id: Id, colour: colour::Key) -> Result<Rc<Texture<'a>>> {
         let tex = Rc::new(self.load(id, colour)?);
         self.textures.insert((id, colour), tex.clone());
@@ -77,12 +193,50 @@ impl<'a> Manager<'a> {
     }
 
     fn load(&mut self, id: Id, colour: colour::Key) -> Result<Texture<'a>> {
-        let path = &self.config(id)?.path;
+        let path = self.config(id)?.path();
         let mut tex = self.creator.load_texture(path).map_err(Error::Load)?;
         self.colourise(&mut tex, colour);
         Ok(tex)
     }
 
+    /// Gets the TrueType face for the given font, loading it if needed.
+    fn face(&mut self, id: Id) -> Result<&ttf::Font<'a, 'static>> {
+        if !self.faces.contains_key(&id) {
+            let face = match self.config(id)? {
+                Config::TrueType { path, size } => {
+                    self.ttf.load_font(path, *size).map_err(Error::Load)?
+                }
+                Config::Bitmap { .. } => return Err(Error::NotTrueType(id)),
+            };
+            self.faces.insert(id, face);
+        }
+        Ok(&self.faces[&id])
+    }
+
+    /// Renders `char` in the given TrueType font and colour.
+    fn render(&mut self, id: Id, colour: colour::Key, char: char) -> Result<Glyph<'a>> {
+        let face = self.face(id)?;
+        let char = provided(face, char);
+        let advance = face.find_glyph_metrics(char).map_or(0, |m| m.advance);
+        let surface = face
+            .render_char(char)
+            .blended(Color::WHITE)
+            .map_err(|e| Error::Render(e.to_string()))?;
+        let (w, h) = (surface.width(), surface.height());
+        let mut tex = self
+            .creator
+            .create_texture_from_surface(&surface)
+            .map_err(|e| Error::Render(e.to_string()))?;
+        self.colourise(&mut tex, colour);
+        Ok(Glyph {
+            texture: Rc::new(tex),
+            src: Rect::new(0, 0, w, h),
+            w,
+            h,
+            advance,
+        })
+    }
+
     fn colourise(&self, texture: &mut Texture, colour: colour::Key) {
         // TODO(@MattWindsor91): decouple colour::SET
         let colour = sdl2::pixels::Color::from(self.colour_set.by_key(colour));
@@ -91,14 +245,39 @@ impl<'a> Manager<'a> {
     }
 }
 
+/// Gets `char` if `face` has a glyph for it, or the replacement character
+/// if not.
+///
+/// SDL only looks up characters in the Basic Multilingual Plane.
+fn provided(face: &ttf::Font, char: char) -> char {
+    let in_bmp = u16::try_from(u32::from(char)).is_ok();
+    if in_bmp && face.find_glyph(char).is_some() {
+        char
+    } else {
+        REPLACEMENT
+    }
+}
+
 /// A key in the font manager's lookup table.
+///
+/// Each key is a role that widgets can give their text; any role that isn't
+/// configured falls back to the normal font.
 #[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, SerializeDisplay, DeserializeFromStr)]
 pub enum Id {
     /// Normal font.
     Normal,
+    /// Large font, for the main timer.
+    Large,
+    /// Small font, for deltas and other secondary information.
+    Small,
+    /// Header font, for the game and category.
+    Header,
 }
 
 const NORMAL_STR: &str = "normal";
+const LARGE_STR: &str = "large";
+const SMALL_STR: &str = "small";
+const HEADER_STR: &str = "header";
 
 impl Display for Id {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -107,6 +286,9 @@ impl Display for Id {
             "{}",
             match self {
                 Self::Normal => NORMAL_STR,
+                Self::Large => LARGE_STR,
+                Self::Small => SMALL_STR,
+                Self::Header => HEADER_STR,
             }
         )
     }
@@ -118,18 +300,52 @@ impl FromStr for Id {
     fn from_str(s: &str) -> Result<Self> {
         match s {
             NORMAL_STR => Ok(Self::Normal),
+            LARGE_STR => Ok(Self::Large),
+            SMALL_STR => Ok(Self::Small),
+            HEADER_STR => Ok(Self::Header),
             _ => Err(Error::Unknown(s.to_owned())),
         }
     }
 }
 
 /// A font configuration.
+///
+/// Which sort of font this is follows from which fields are present.
 #[derive(Serialize, Deserialize, Debug)]
-pub struct Config {
-    /// The font path.
-    path: String,
-    /// The font metrics.
-    metrics: metrics::Font,
+#[serde(untagged)]
+pub enum Config {
+    /// A bitmap font, laid out as a grid of the 256 Latin-1 characters.
+    Bitmap {
+        /// The font path.
+        path: String,
+        /// The font metrics.
+        metrics: metrics::Font,
+        /// The factor by which to scale the font up on screen.
+        #[serde(default = "default_scale")]
+        scale: u8,
+    },
+    /// A TrueType (or OpenType) font.
+    TrueType {
+        /// The font path.
+        path: String,
+        /// The font size, in points.
+        size: u16,
+    },
+}
+
+impl Config {
+    /// Gets the path of the font.
+    #[must_use]
+    pub fn path(&self) -> &str {
+        match self {
+            Self::Bitmap { path, .. } | Self::TrueType { path, .. } => path,
+        }
+    }
+}
+
+/// Bitmap fonts are shown at their own size by default.
+fn default_scale() -> u8 {
+    1
 }
 
 /// A font error.
@@ -143,6 +359,14 @@ pub enum Error {
     #[error("font not configured: {0}")]
     Config(Id),
 
+    /// An error occurred while rendering a TrueType glyph.
+    #[error("couldn't render glyph: {0}")]
+    Render(String),
+
+    /// We tried to use a bitmap font as a TrueType font.
+    #[error("font not TrueType: {0}")]
+    NotTrueType(Id),
+
     /// We tried to configure a font using a nonexistent ID.
     #[error("font id not recognised: {0}")]
     Unknown(String),
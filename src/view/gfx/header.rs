@@ -2,27 +2,39 @@
 
 use super::{
     colour, font, metrics,
-    position::{Position, X},
+    position::{Position, X, Y},
     render::{Region, Renderer},
+    split::time_str,
     widget,
 };
-use crate::{model::run::Metadata, presenter::Presenter, view::error::Result};
+use crate::{
+    model::{practice::Stats, run::Metadata, session::Session, time::Time},
+    presenter::Presenter,
+    view::error::Result,
+};
 
 /// Views information about the run in the form of a header.
 pub struct Widget {
     /// The bounding box for the header widget.
     pub rect: metrics::Rect,
+    /// The font for the header's text.
+    pub font: font::Id,
 }
 
 impl widget::Widget for Widget {
     fn render(&mut self, r: &mut dyn Renderer, p: &Presenter) -> Result<()> {
         let mut r = Region::new(r, self.rect);
 
-        r.set_font(font::Id::Normal)?;
+        r.set_font(self.font)?;
         r.set_fg_colour(colour::Key::Header);
 
         render_meta(&mut r, &p.run.metadata)?;
         render_attempt(&mut r, p.run.attempt)?;
+        render_golds(&mut r, p.run.golds())?;
+        match p.practice_stats() {
+            Some(stats) => render_line(&mut r, &practice_str(&stats))?,
+            None => render_line(&mut r, &session_str(&p.session))?,
+        }
         Ok(())
     }
 }
@@ -39,3 +51,41 @@ fn render_attempt(r: &mut dyn Renderer, attempt: usize) -> Result<()> {
     r.set_pos(Position::x(X::Right(0)));
     r.put_str_r(&format!("#{}", attempt))
 }
+
+/// Renders the number of gold splits in the run, on the first line.
+fn render_golds(r: &mut dyn Renderer, golds: usize) -> Result<()> {
+    r.set_pos(Position {
+        x: X::Right(0),
+        y: Y::Top(0),
+    });
+    r.put_str_r(&format!("Golds {golds}"))
+}
+
+/// Renders `line` on the third line of the header.
+fn render_line(r: &mut dyn Renderer, line: &str) -> Result<()> {
+    r.set_pos(Position::top_left(0, 0));
+    r.move_chars(0, 2);
+    r.put_str(line)
+}
+
+fn session_str(session: &Session) -> String {
+    format!(
+        "Session {}/{} best {}",
+        session.completions,
+        session.attempts,
+        best_str(session.best)
+    )
+}
+
+fn practice_str(stats: &Stats) -> String {
+    format!(
+        "Practice {}/{} best {}",
+        stats.completions,
+        stats.attempts,
+        best_str(stats.best)
+    )
+}
+
+fn best_str(best: Option<Time>) -> String {
+    best.map_or_else(|| "--'--\"---".to_owned(), time_str)
+}
@@ -0,0 +1,428 @@
+//! Configurable layout of the widgets in the window.
+//!
+//! A layout is a stack of items running either down or across the window.
+//! Each item is either a widget or another stack, and takes up either a fixed
+//! amount of room along the stack or a share of whatever room is left over.
+
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+
+use super::{
+    font,
+    metrics::{sat_i32, Rect, Window},
+};
+
+/// The widgets that can appear in a layout.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Kind {
+    /// The header, showing the game, category, and attempt information.
+    Header,
+    /// The split viewer.
+    Splits,
+    /// The extra widgets, one row each, in the order configured.
+    Extras,
+    /// The total time.
+    Total,
+    /// The status line, which doubles as the command line.
+    Status,
+}
+
+impl Kind {
+    /// Gets the font the widget uses for its main text, unless the layout
+    /// says otherwise.
+    #[must_use]
+    pub fn default_font(self) -> font::Id {
+        match self {
+            Self::Header => font::Id::Header,
+            Self::Splits | Self::Status => font::Id::Normal,
+            Self::Extras => font::Id::Small,
+            Self::Total => font::Id::Large,
+        }
+    }
+}
+
+/// A widget placed by a layout.
+#[derive(Copy, Clone, Debug)]
+pub struct Placed {
+    /// Which widget this is.
+    pub kind: Kind,
+    /// The rectangle the widget should render into.
+    pub rect: Rect,
+    /// The font the widget should use for its main text.
+    pub font: font::Id,
+}
+
+/// The direction in which a stack runs.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Direction {
+    /// Items run from the top of the stack to the bottom.
+    Vertical,
+    /// Items run from the left of the stack to the right.
+    Horizontal,
+}
+
+/// Stacks run down the window by default.
+impl Default for Direction {
+    fn default() -> Self {
+        Self::Vertical
+    }
+}
+
+impl Direction {
+    /// Gets the length of `rect` along this direction.
+    fn length(self, rect: Rect) -> u32 {
+        match self {
+            Self::Vertical => rect.h,
+            Self::Horizontal => rect.w,
+        }
+    }
+
+    /// Gets the direction across this one.
+    fn across(self) -> Self {
+        match self {
+            Self::Vertical => Self::Horizontal,
+            Self::Horizontal => Self::Vertical,
+        }
+    }
+
+    /// Cuts the slice of `rect` that starts `offset` pixels along this
+    /// direction and is `length` pixels long.
+    fn slice(self, rect: Rect, offset: u32, length: u32) -> Rect {
+        match self {
+            Self::Vertical => Rect {
+                y: rect.y + sat_i32(offset),
+                h: length,
+                ..rect
+            },
+            Self::Horizontal => Rect {
+                x: rect.x + sat_i32(offset),
+                w: length,
+                ..rect
+            },
+        }
+    }
+}
+
+/// Where an item narrower than its stack sits across the stack.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Align {
+    /// The item sits at the top or left.
+    Start,
+    /// The item sits in the middle.
+    Centre,
+    /// The item sits at the bottom or right.
+    End,
+}
+
+/// Items sit at the start of their stack by default.
+impl Default for Align {
+    fn default() -> Self {
+        Self::Start
+    }
+}
+
+/// A stack of items; the layout as a whole is one of these.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Stack {
+    /// The direction in which the items run.
+    #[serde(default)]
+    pub direction: Direction,
+    /// The items, in order.
+    pub items: Vec<Item>,
+}
+
+/// The default layout stacks the header, splits, extras, total, and status
+/// line down the window.
+impl Default for Stack {
+    fn default() -> Self {
+        let items = [
+            Kind::Header,
+            Kind::Splits,
+            Kind::Extras,
+            Kind::Total,
+            Kind::Status,
+        ];
+        Self {
+            direction: Direction::Vertical,
+            items: items.iter().copied().map(Item::widget).collect(),
+        }
+    }
+}
+
+impl Stack {
+    /// Lays out this stack over the whole window described by `wmetrics`,
+    /// when there are `extras` extra widgets.
+    ///
+    /// Returns each widget in the layout with the rectangle it should render
+    /// into, in layout order.
+    #[must_use]
+    pub fn lay_out(&self, wmetrics: &Window, extras: usize) -> Vec<Placed> {
+        let mut placed = Vec::with_capacity(self.items.len());
+        self.lay_out_in(wmetrics.rect(), wmetrics, extras, None, &mut placed);
+        placed
+    }
+
+    /// Lays out this stack over `rect`, pushing the widgets to `placed`.
+    ///
+    /// Widgets without a font of their own use `font`, if given.
+    fn lay_out_in(
+        &self,
+        rect: Rect,
+        wmetrics: &Window,
+        extras: usize,
+        font: Option<font::Id>,
+        placed: &mut Vec<Placed>,
+    ) {
+        let sizes: Vec<Option<u32>> = self
+            .items
+            .iter()
+            .map(|i| i.size(self.direction, wmetrics, extras))
+            .collect();
+        let fixed: u32 = sizes.iter().flatten().sum();
+        let mut leftover = self.direction.length(rect).saturating_sub(fixed);
+        let mut fills = sizes.iter().filter(|s| s.is_none()).count();
+
+        let mut offset = 0;
+        for (item, size) in self.items.iter().zip(sizes) {
+            let length = size.unwrap_or_else(|| {
+                // The last item to fill takes any rounding leftovers.
+                let share = leftover / u32::try_from(fills).unwrap_or(u32::MAX);
+                fills -= 1;
+                leftover -= share;
+                share
+            });
+            let slot = self.direction.slice(rect, offset, length);
+            item.place(slot, self.direction, wmetrics, extras, font, placed);
+            offset += length;
+        }
+    }
+}
+
+/// An item in a stack.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Item {
+    /// What the item holds.
+    #[serde(flatten)]
+    pub content: Content,
+    /// The length of the item along its stack, in pixels.
+    ///
+    /// If missing, widgets in vertical stacks take their heights from the
+    /// window metrics; the splits, and anything else, share the room left
+    /// over.
+    pub size: Option<u32>,
+    /// The breadth of the item across its stack, in pixels.
+    ///
+    /// If missing, the item takes up the whole breadth of the stack.
+    pub breadth: Option<u32>,
+    /// Where the item sits across its stack, if it is narrower than it.
+    #[serde(default)]
+    pub align: Align,
+    /// The padding inside the item.
+    ///
+    /// If missing, widgets take the standard padding from the window metrics,
+    /// and stacks have none.
+    pub padding: Option<u32>,
+    /// The font for the main text of the widget, or of every widget in the
+    /// stack.
+    ///
+    /// If missing, widgets use the font of the stack they are in, or their
+    /// own default font.
+    pub font: Option<font::Id>,
+}
+
+/// The contents of an item.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Content {
+    /// A widget.
+    Widget {
+        /// Which widget this is.
+        widget: Kind,
+    },
+    /// A nested stack.
+    Stack(Stack),
+}
+
+impl Item {
+    /// Constructs an item holding `widget` with default metrics.
+    #[must_use]
+    pub fn widget(widget: Kind) -> Self {
+        Self {
+            content: Content::Widget { widget },
+            size: None,
+            breadth: None,
+            align: Align::default(),
+            padding: None,
+            font: None,
+        }
+    }
+
+    /// Gets the fixed length of this item in a stack running in direction
+    /// `dir`, or `None` if it should share the leftover room.
+    fn size(&self, dir: Direction, wmetrics: &Window, extras: usize) -> Option<u32> {
+        if self.size.is_some() || dir == Direction::Horizontal {
+            return self.size;
+        }
+        match self.content {
+            Content::Widget { widget } => match widget {
+                Kind::Header => Some(wmetrics.header_h),
+                Kind::Splits => None,
+                Kind::Extras => Some(wmetrics.extras_h(extras)),
+                Kind::Total => Some(wmetrics.total_h),
+                Kind::Status => Some(wmetrics.status_h),
+            },
+            Content::Stack(_) => None,
+        }
+    }
+
+    /// Places this item in `slot`, which belongs to a stack running in
+    /// direction `dir` with font `font`, pushing the widgets inside it to
+    /// `placed`.
+    fn place(
+        &self,
+        slot: Rect,
+        dir: Direction,
+        wmetrics: &Window,
+        extras: usize,
+        font: Option<font::Id>,
+        placed: &mut Vec<Placed>,
+    ) {
+        let across = dir.across();
+        let room = across.length(slot);
+        let breadth = self.breadth.map_or(room, |b| b.min(room));
+        let offset = match self.align {
+            Align::Start => 0,
+            Align::Centre => (room - breadth) / 2,
+            Align::End => room - breadth,
+        };
+        let slot = across.slice(slot, offset, breadth);
+
+        let font = self.font.or(font);
+        match &self.content {
+            Content::Widget { widget } => placed.push(Placed {
+                kind: *widget,
+                rect: slot.pad(self.padding.unwrap_or(wmetrics.padding)),
+                font: font.unwrap_or_else(|| widget.default_font()),
+            }),
+            Content::Stack(stack) => {
+                let rect = slot.pad(self.padding.unwrap_or(0));
+                stack.lay_out_in(rect, wmetrics, extras, font, placed);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wmetrics() -> Window {
+        Window {
+            win_w: 320,
+            win_h: 640,
+            padding: 4,
+            header_h: 40,
+            total_h: 32,
+            status_h: 16,
+            split_h: 16,
+            always_on_top: false,
+            borderless: false,
+        }
+    }
+
+    fn rect(x: i32, y: i32, w: u32, h: u32) -> (i32, i32, u32, u32) {
+        (x, y, w, h)
+    }
+
+    fn rects(placed: &[Placed]) -> Vec<(i32, i32, u32, u32)> {
+        placed
+            .iter()
+            .map(|p| rect(p.rect.x, p.rect.y, p.rect.w, p.rect.h))
+            .collect()
+    }
+
+    fn fonts(placed: &[Placed]) -> Vec<font::Id> {
+        placed.iter().map(|p| p.font).collect()
+    }
+
+    /// Tests that the default layout stacks the widgets down the window.
+    #[test]
+    fn default_layout() {
+        let placed = Stack::default().lay_out(&wmetrics(), 2);
+        let kinds: Vec<Kind> = placed.iter().map(|p| p.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                Kind::Header,
+                Kind::Splits,
+                Kind::Extras,
+                Kind::Total,
+                Kind::Status
+            ]
+        );
+        assert_eq!(
+            rects(&placed),
+            vec![
+                rect(4, 4, 312, 32),
+                rect(4, 44, 312, 504),
+                rect(4, 556, 312, 32),
+                rect(4, 596, 312, 24),
+                rect(4, 628, 312, 8),
+            ]
+        );
+        assert_eq!(
+            fonts(&placed),
+            vec![
+                font::Id::Header,
+                font::Id::Normal,
+                font::Id::Small,
+                font::Id::Large,
+                font::Id::Normal
+            ]
+        );
+    }
+
+    /// Tests a horizontal layout with a narrower, centred, nested stack,
+    /// whose font applies to the widgets inside it.
+    #[test]
+    fn horizontal_layout() {
+        let side = Item {
+            content: Content::Stack(Stack {
+                direction: Direction::Vertical,
+                items: vec![Item::widget(Kind::Header), Item::widget(Kind::Total)],
+            }),
+            size: Some(100),
+            breadth: Some(200),
+            align: Align::Centre,
+            padding: None,
+            font: Some(font::Id::Small),
+        };
+
+        let layout = Stack {
+            direction: Direction::Horizontal,
+            items: vec![Item::widget(Kind::Splits), side, Item::widget(Kind::Status)],
+        };
+        let placed = layout.lay_out(&wmetrics(), 0);
+        assert_eq!(
+            rects(&placed),
+            vec![
+                rect(4, 4, 102, 632),
+                rect(114, 224, 92, 32),
+                rect(114, 264, 92, 24),
+                rect(214, 4, 102, 632),
+            ]
+        );
+        assert_eq!(
+            fonts(&placed),
+            vec![
+                font::Id::Normal,
+                font::Id::Small,
+                font::Id::Small,
+                font::Id::Normal
+            ]
+        );
+    }
+}
@@ -3,8 +3,6 @@ use serde::{Deserialize, Serialize};
 
 use std::convert::TryFrom;
 
-pub(super) const TIME_CHARS: i32 = 9; // XX'XX"XXX
-
 /// Font metrics.
 #[derive(Copy, Clone, Debug, Deserialize, Serialize)]
 pub struct Font {
@@ -34,6 +32,19 @@ impl Font {
         self.char_h + self.pad_h
     }
 
+    /// These metrics, with everything but the column count multiplied by
+    /// `scale`.
+    #[must_use]
+    pub fn scaled(self, scale: u8) -> Self {
+        Self {
+            char_w: self.char_w.saturating_mul(scale),
+            char_h: self.char_h.saturating_mul(scale),
+            pad_w: self.pad_w.saturating_mul(scale),
+            pad_h: self.pad_h.saturating_mul(scale),
+            ..self
+        }
+    }
+
     /// The column of a glyph in the font.
     #[must_use]
     pub fn glyph_col(self, char: u8) -> u8 {
@@ -80,72 +91,52 @@ pub struct Window {
     pub win_h: u32,
     /// Standard padding on contents.
     pub padding: u32,
-    /// The height of the header.
+    /// The height of the header, when in a vertical stack.
     pub header_h: u32,
-    /// The height of the total section.
+    /// The height of the total section, when in a vertical stack.
     pub total_h: u32,
+    /// The height of the status line, when in a vertical stack.
+    pub status_h: u32,
     /// The height of one split.
     pub split_h: u32,
+    /// Whether the window should stay above other windows.
+    #[serde(default)]
+    pub always_on_top: bool,
+    /// Whether the window should have no border or title bar.
+    #[serde(default)]
+    pub borderless: bool,
 }
 
 impl Window {
-    /// Gets the bounding box of the header part of the window.
+    /// Gets a copy of these metrics for a window of size `w` by `h`.
     #[must_use]
-    pub fn header_rect(&self) -> Rect {
-        Rect {
-            x: 0,
-            y: 0,
-            w: self.win_w,
-            h: self.header_h,
+    pub fn resized(self, w: u32, h: u32) -> Self {
+        Self {
+            win_w: w,
+            win_h: h,
+            ..self
         }
-        .pad(self.padding)
     }
 
-    /// Gets the bounding box of the splits part of the window.
+    /// Gets the bounding box of the whole window.
     #[must_use]
-    pub fn splits_rect(&self) -> Rect {
+    pub fn rect(&self) -> Rect {
         Rect {
             x: 0,
-            y: self.splits_y(),
+            y: 0,
             w: self.win_w,
-            h: self.splits_h(),
+            h: self.win_h,
         }
-        .pad(self.padding)
     }
 
-    /// Gets the bounding box of the total part of the window.
+    /// Gets the height of the extra widgets part of the window, when there
+    /// are `extras` extra widgets.
     #[must_use]
-    pub fn total_rect(&self) -> Rect {
-        Rect {
-            x: 0,
-            y: self.total_y(),
-            w: self.win_w,
-            h: self.total_h,
+    pub fn extras_h(&self, extras: usize) -> u32 {
+        if extras == 0 {
+            return 0;
         }
-        .pad(self.padding)
-    }
-
-    /// Gets the unshifted bounding box of the editor part of the window.
-    #[must_use]
-    pub fn editor_rect(&self) -> Rect {
-        let mut r = self.splits_rect();
-        r.h = self.split_h;
-        r
-    }
-
-    /// Gets the Y position of the splits part of the window.
-    fn splits_y(&self) -> i32 {
-        sat_i32(self.header_h)
-    }
-
-    /// Gets the Y position of the total part of the window.
-    fn total_y(&self) -> i32 {
-        sat_i32(self.win_h - self.total_h)
-    }
-
-    /// Gets the height of the splits part of the window.
-    fn splits_h(&self) -> u32 {
-        self.win_h - self.header_h - self.total_h
+        self.split_h * u32::try_from(extras).unwrap_or(u32::MAX) + (self.padding * 2)
     }
 }
 
@@ -189,8 +180,8 @@ impl Rect {
         Self {
             x: self.x + sat_i32(amount),
             y: self.y + sat_i32(amount),
-            w: self.w - (amount * 2),
-            h: self.h - (amount * 2),
+            w: self.w.saturating_sub(amount * 2),
+            h: self.h.saturating_sub(amount * 2),
         }
     }
 }
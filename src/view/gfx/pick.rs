@@ -0,0 +1,28 @@
+//! Drawing game and category pickers.
+
+use super::{
+    colour, font,
+    position::{Position, X},
+    render::Renderer,
+};
+use crate::{
+    presenter::{cursor::SplitPosition, pick::Choice},
+    view::error::Result,
+};
+
+/// Draws `choice` at `position` relative to the picker's cursor, with its
+/// name on the left and its ID on the right, in font `font`.
+pub(super) fn draw_choice(
+    r: &mut dyn Renderer,
+    choice: &Choice,
+    position: SplitPosition,
+    font: font::Id,
+) -> Result<()> {
+    r.set_font(font)?;
+    r.set_fg_colour(colour::Key::Name(position));
+    r.put_str(&choice.name)?;
+
+    r.set_pos(Position::x(X::Right(0)));
+    r.set_fg_colour(colour::Key::NoTime);
+    r.put_str_r(&choice.id)
+}
@@ -1,12 +1,12 @@
 //! The low-level graphics rendering layer.
 
-use std::{cell::RefMut, rc::Rc};
+use std::cell::RefMut;
 
 use super::super::error::{Error, Result};
 use super::{colour, font, metrics, position::Position};
 use sdl2::{
     rect::{Point, Rect},
-    render::{Canvas, Texture},
+    render::Canvas,
     video,
 };
 
@@ -28,6 +28,12 @@ pub trait Renderer {
     /// Sets the current foreground colour.
     fn set_fg_colour(&mut self, colour: colour::Key);
 
+    /// Measures the width of `str`, in pixels, in the current font.
+    fn str_w(&self, str: &str) -> i32;
+
+    /// Gets the width, in pixels, of the area into which we are rendering.
+    fn width(&self) -> u32;
+
     /// Puts a string `str` onto the screen at the current coordinate.
     ///
     /// # Errors
@@ -44,10 +50,10 @@ pub trait Renderer {
     /// Returns an error if SDL fails to load the font (if it has not been
     /// loaded already), or fails to blit the font onto the screen.
     fn put_str_r(&mut self, str: &str) -> Result<()> {
-        let len = metrics::sat_i32(str.len());
-        self.move_chars(-len, 0);
+        let w = self.str_w(str);
+        self.set_pos(Position::rel(-w, 0));
         self.put_str(str)?;
-        self.move_chars(len, 0);
+        self.set_pos(Position::rel(w, 0));
         Ok(())
     }
 }
@@ -97,16 +103,26 @@ impl<'a> Renderer for Window<'a> {
         self.colour = colour
     }
 
-    fn put_str(&mut self, str: &str) -> Result<()> {
-        let old_pos = self.pos;
-        let texture = self.font_texture()?;
+    fn str_w(&self, str: &str) -> i32 {
+        self.font_manager.str_w(self.font, str)
+    }
+
+    fn width(&self) -> u32 {
+        self.w_metrics.win_w
+    }
 
-        for byte in str.as_bytes() {
-            self.put_byte(&texture, *byte, self.pos)?;
-            self.move_chars(1, 0);
+    fn put_str(&mut self, str: &str) -> Result<()> {
+        let mut top_left = self.pos;
+
+        for char in str.chars() {
+            let glyph = self.font_manager.glyph(self.font, self.colour, char)?;
+            let dst = Rect::new(top_left.x, top_left.y, glyph.w, glyph.h);
+            self.screen
+                .copy(&glyph.texture, glyph.src, dst)
+                .map_err(Error::Blit)?;
+            top_left = top_left.offset(glyph.advance, 0);
         }
 
-        self.pos = old_pos;
         Ok(())
     }
 }
@@ -120,7 +136,7 @@ impl<'a> Window<'a> {
     pub fn new(
         screen: RefMut<'a, Canvas<video::Window>>,
         w_metrics: metrics::Window,
-        font_manager: font::Manager<'a>,
+        mut font_manager: font::Manager<'a>,
         colour_set: &'a colour::Set,
     ) -> Result<Self> {
         let font = font::Id::Normal;
@@ -138,6 +154,12 @@ impl<'a> Window<'a> {
         })
     }
 
+    /// Replaces the window metrics with `w_metrics`, for instance when the
+    /// window has been resized.
+    pub fn set_metrics(&mut self, w_metrics: metrics::Window) {
+        self.w_metrics = w_metrics;
+    }
+
     /// Clears the screen.
     pub fn clear(&mut self) {
         self.screen
@@ -149,39 +171,6 @@ impl<'a> Window<'a> {
     pub fn present(&mut self) {
         self.screen.present()
     }
-
-    fn font_texture(&mut self) -> Result<Rc<Texture<'a>>> {
-        Ok(self.font_manager.texture(self.font, self.colour)?)
-    }
-
-    fn put_byte<'b>(
-        &'b mut self,
-        texture: &'b Texture<'a>,
-        byte: u8,
-        top_left: Point,
-    ) -> Result<()> {
-        let src = self.font_rect(byte);
-        let dst = self.char_rect(top_left);
-        self.screen.copy(texture, src, dst).map_err(Error::Blit)
-    }
-
-    /// Produces a rectangle with top-left `top_left` and the size of one font
-    /// character.
-    #[must_use]
-    fn char_rect(&self, top_left: Point) -> Rect {
-        let w = self.f_metrics.char_w;
-        let h = self.f_metrics.char_h;
-        Rect::new(top_left.x, top_left.y, u32::from(w), u32::from(h))
-    }
-
-    /// Produces the appropriate rectangle for looking up `char` in the font.
-    #[must_use]
-    fn font_rect(&self, char: u8) -> Rect {
-        self.char_rect(Point::new(
-            self.f_metrics.glyph_x(char),
-            self.f_metrics.glyph_y(char),
-        ))
-    }
 }
 
 /// A renderer that delegates to an underlying renderer, but maps coordinates
@@ -213,6 +202,12 @@ impl<'a> Renderer for Region<'a> {
     fn set_fg_colour(&mut self, colour: colour::Key) {
         self.renderer.set_fg_colour(colour)
     }
+    fn str_w(&self, str: &str) -> i32 {
+        self.renderer.str_w(str)
+    }
+    fn width(&self) -> u32 {
+        self.rect.w
+    }
     fn put_str(&mut self, str: &str) -> Result<()> {
         self.renderer.put_str(str)
     }
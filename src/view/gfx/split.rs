@@ -1,99 +1,371 @@
 //! Logic for drawing splits.
 
+mod viewport;
+
 use std::convert::TryFrom;
 
+use serde::{Deserialize, Serialize};
+
 use super::{
-    colour, font, metrics,
+    colour, editor, finish, flash, font, metrics, pick,
     position::{Position, X},
     render::{Region, Renderer},
 };
 use crate::{
     model,
-    presenter::{cursor, Presenter},
+    presenter::{cursor, finish::Summary, flash::Target, pick::Picker, Presenter},
     view::error::Result,
 };
 
+/// Configuration for the split viewer.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct Config {
+    /// Whether to collapse groups that don't contain the cursor into a single
+    /// row.
+    #[serde(default)]
+    pub collapse_groups: bool,
+    /// The number of rows to keep in view before the cursor when scrolling.
+    #[serde(default = "default_previous")]
+    pub previous: usize,
+    /// The number of rows to keep in view after the cursor when scrolling.
+    #[serde(default = "default_upcoming")]
+    pub upcoming: usize,
+    /// Whether to keep the final row in view at the bottom when scrolling.
+    #[serde(default = "default_pin_last")]
+    pub pin_last: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            collapse_groups: false,
+            previous: default_previous(),
+            upcoming: default_upcoming(),
+            pin_last: default_pin_last(),
+        }
+    }
+}
+
+fn default_previous() -> usize {
+    1
+}
+
+fn default_upcoming() -> usize {
+    2
+}
+
+fn default_pin_last() -> bool {
+    true
+}
+
 /// The split viewer widget.
 pub struct Widget {
     /// The bounding box used for the widget.
     rect: metrics::Rect,
     /// The height of one split.
     split_h: i32,
+    /// The split viewer configuration.
+    cfg: Config,
+    /// The viewport used to scroll the rows.
+    viewport: viewport::Viewport,
+    /// The font for the rows.
+    font: font::Id,
 }
 
 impl super::widget::Widget for Widget {
     fn render(&mut self, r: &mut dyn Renderer, p: &Presenter) -> Result<()> {
-        let mut r = Region::new(r, self.rect);
-
-        for (index, split) in p.run.splits.iter().enumerate() {
-            r.set_pos(self.split_pos(index));
-            SplitDrawer {
-                index,
-                r: &mut r,
-                p,
-                split,
+        if let Some(picker) = p.picker() {
+            return self.render_picker(r, picker);
+        }
+        if let Some(summary) = p.summary() {
+            let dim = flash::is_dimmed(p, Target::PersonalBest);
+            return self.render_summary(r, summary, &p.run, dim);
+        }
+        for (index, row) in self.visible_rows(p).into_iter().enumerate() {
+            let mut r = Region::new(r, self.row_rect(index));
+            r.set_pos(Position::top_left(0, 0));
+            match row {
+                Row::Above(n) => draw_more(&mut r, '^', n, self.font)?,
+                Row::Below(n) => draw_more(&mut r, 'v', n, self.font)?,
+                Row::Group(group) => draw_group(&mut r, p, group, self.font)?,
+                Row::Split(index) => SplitDrawer {
+                    index,
+                    r: &mut r,
+                    p,
+                    split: &p.run.splits[index],
+                    font: self.font,
+                }
+                .draw()?,
             }
-            .draw()?;
         }
         Ok(())
     }
 }
 
 impl Widget {
-    /// Creates a new view using the given bounding box and split height.
-    pub fn new(rect: metrics::Rect, split_h: i32) -> Self {
-        Self { rect, split_h }
+    /// Creates a new view using the given bounding box, split height,
+    /// configuration, and font.
+    #[must_use]
+    pub fn new(rect: metrics::Rect, split_h: i32, cfg: Config, font: font::Id) -> Self {
+        Self {
+            rect,
+            split_h,
+            cfg,
+            viewport: viewport::Viewport::default(),
+            font,
+        }
+    }
+
+    /// Renders the choices of `picker` in place of the splits.
+    fn render_picker(&mut self, r: &mut dyn Renderer, picker: &Picker) -> Result<()> {
+        let range = self.viewport.scroll(
+            picker.cursor().position(),
+            picker.choices.len(),
+            self.capacity(),
+            self.cfg.previous.max(1),
+            self.cfg.upcoming.max(1),
+        );
+        for (row, index) in range.enumerate() {
+            let mut r = Region::new(r, self.row_rect(row));
+            r.set_pos(Position::top_left(0, 0));
+            let position = picker.cursor().split_position(index);
+            pick::draw_choice(&mut r, &picker.choices[index], position, self.font)?;
+        }
+        Ok(())
+    }
+
+    /// Renders `summary`, of the finished run `run`, in place of the splits,
+    /// dimming any personal best if `dim`.
+    fn render_summary(
+        &mut self,
+        r: &mut dyn Renderer,
+        summary: &Summary,
+        run: &model::run::Run,
+        dim: bool,
+    ) -> Result<()> {
+        let rows = finish::rows(summary, run, dim);
+        for (index, row) in rows.iter().take(self.capacity()).enumerate() {
+            let mut r = Region::new(r, self.row_rect(index));
+            r.set_pos(Position::top_left(0, 0));
+            finish::draw_row(&mut r, row, self.font)?;
+        }
+        Ok(())
+    }
+
+    /// Works out which rows fit in the widget for the presenter `p`,
+    /// scrolling to keep the cursor in view.
+    fn visible_rows(&mut self, p: &Presenter) -> Vec<Row> {
+        let mut rows = self.rows(p);
+        let mut cap = self.capacity();
+        if rows.len() <= cap {
+            return rows;
+        }
+
+        let focus = rows
+            .iter()
+            .position(|r| matches!(r, Row::Split(i) if *i == focus_split(p)))
+            .unwrap_or_default();
+
+        let pinned = if self.cfg.pin_last && 1 < cap {
+            cap -= 1;
+            rows.pop()
+        } else {
+            None
+        };
+
+        let range = self.viewport.scroll(
+            focus,
+            rows.len(),
+            cap,
+            self.cfg.previous.max(1),
+            self.cfg.upcoming.max(1),
+        );
+        let mut visible = rows[range.clone()].to_vec();
+        // We replace the rows at either end with indicators if there are rows
+        // out of view; the viewport keeps the cursor clear of them, as long as
+        // there is enough space.
+        if 3 <= cap {
+            if 0 < range.start {
+                visible[0] = Row::Above(range.start + 1);
+            }
+            if range.end < rows.len() {
+                visible[cap - 1] = Row::Below(rows.len() - range.end + 1);
+            }
+        }
+        visible.extend(pinned);
+        visible
+    }
+
+    /// Gets the number of rows that fit in the widget.
+    fn capacity(&self) -> usize {
+        usize::try_from(metrics::sat_i32(self.rect.h) / self.split_h.max(1)).unwrap_or_default()
     }
 
-    fn split_pos(&self, index: usize) -> Position {
-        Position::top_left(0, i32::try_from(index).unwrap_or_default() * self.split_h)
+    /// Works out which rows to show for the presenter `p`.
+    ///
+    /// Each group gets a header row, followed by its splits (and subgroups)
+    /// unless it is collapsed.
+    fn rows(&self, p: &Presenter) -> Vec<Row> {
+        let focus = focus_split(p);
+        let groups = &p.run.groups;
+        let is_expanded = |g: &model::group::Group| !self.cfg.collapse_groups || g.contains(focus);
+
+        let mut rows = Vec::with_capacity(p.run.splits.len() + groups.len());
+        for index in 0..p.run.splits.len() {
+            // Groups are stored outermost first, so headers come out in the
+            // right order.
+            for (gi, g) in groups.iter().enumerate().filter(|(_, g)| g.start == index) {
+                let is_visible = groups
+                    .iter()
+                    .filter(|h| h.depth < g.depth && h.contains(index))
+                    .all(is_expanded);
+                if is_visible {
+                    rows.push(Row::Group(gi));
+                }
+            }
+            if groups.iter().filter(|g| g.contains(index)).all(is_expanded) {
+                rows.push(Row::Split(index));
+            }
+        }
+        rows
+    }
+
+    fn row_rect(&self, index: usize) -> metrics::Rect {
+        let mut rect = self.rect;
+        rect.y += i32::try_from(index).unwrap_or_default() * self.split_h;
+        rect.h = u32::try_from(self.split_h).unwrap_or_default();
+        rect
     }
 }
 
+/// Gets the split on which the split viewer should focus: the cursor, if
+/// there is one, or the first split otherwise.
+fn focus_split(p: &Presenter) -> usize {
+    p.mode.cursor().map_or(0, cursor::Cursor::position)
+}
+
+/// A row in the split viewer.
+#[derive(Copy, Clone, Debug)]
+enum Row {
+    /// An indicator that the given number of rows are out of view above.
+    Above(usize),
+    /// An indicator that the given number of rows are out of view below.
+    Below(usize),
+    /// A header for the group with the given index.
+    Group(usize),
+    /// The split with the given index.
+    Split(usize),
+}
+
+/// Draws an indicator, with arrow `arrow`, that `n` rows are out of view.
+fn draw_more(r: &mut dyn Renderer, arrow: char, n: usize, font: font::Id) -> Result<()> {
+    r.set_font(font)?;
+    r.set_fg_colour(colour::Key::NoTime);
+    r.put_str(&format!("{arrow} {n} more"))
+}
+
+/// Draws the header row for the group at index `group`.
+fn draw_group(r: &mut dyn Renderer, p: &Presenter, group: usize, font: font::Id) -> Result<()> {
+    let group_data = &p.run.groups[group];
+    r.set_font(font)?;
+    r.set_fg_colour(colour::Key::Header);
+    r.move_chars(metrics::sat_i32(group_data.depth), 0);
+    r.put_str(&group_data.name)?;
+
+    let model::pace::PacedTime { pace, time } = p.run.group_paced_time_at(group);
+    r.set_pos(Position::x(X::Right(0)));
+    r.set_fg_colour(colour::Key::Pace(pace));
+    r.put_str_r(&time_str(time))
+}
+
 /// Contains all state useful to draw one split.
 struct SplitDrawer<'r, 'g, 'p, 's> {
     index: usize,
     r: &'r mut Region<'g>,
     p: &'p Presenter,
     split: &'s model::split::Split,
+    font: font::Id,
 }
 
 impl<'r, 'g, 'p, 's> SplitDrawer<'r, 'g, 'p, 's> {
     fn draw(&mut self) -> Result<()> {
         self.draw_name()?;
-        self.draw_time()?;
-        Ok(())
+        match self.editor() {
+            Some(e) => editor::draw(self.r, e, self.font),
+            None => self.draw_time(),
+        }
     }
 
     fn draw_name(&mut self) -> Result<()> {
-        self.r.set_font(font::Id::Normal)?;
-        self.r.set_fg_colour(colour::Key::Name(self.position()));
-        self.r.put_str(&self.split.name)?;
+        self.r.set_font(self.font)?;
+        self.r.set_fg_colour(self.name_colour());
+        self.r
+            .move_chars(metrics::sat_i32(self.p.run.depth_of(self.index)), 0);
+        let name = self.name();
+        self.r.put_str(name)?;
         Ok(())
     }
 
+    /// Gets the name to show for the split, falling back to the short name if
+    /// the full name would run into the time.
+    fn name(&self) -> &'s str {
+        let indent = self.r.str_w(&" ".repeat(self.p.run.depth_of(self.index)));
+        let avail = metrics::sat_i32(self.r.width()) - indent - self.r.str_w(PLACEHOLDER_TIME);
+        if self.r.str_w(&self.split.name) < avail {
+            &self.split.name
+        } else {
+            self.split.short_name()
+        }
+    }
+
     fn draw_time(&mut self) -> Result<()> {
         self.r.set_pos(Position::x(X::Right(0)));
-        if self.split.has_times() {
-            self.draw_summed_time()
-        } else {
-            self.draw_time_placeholder()
+        match self.split.status() {
+            model::split::Status::Completed => self.draw_summed_time(),
+            model::split::Status::Skipped => self.draw_skipped(),
+            model::split::Status::NotReached => self.draw_time_placeholder(),
         }
     }
 
     fn draw_summed_time(&mut self) -> Result<()> {
         // TODO(@MattWindsor91): hours?
-        self.r.set_font(font::Id::Normal)?;
+        self.r.set_font(self.font)?;
         // TODO(@MattWindsor91): use both dimensions of pace.
         let model::pace::Pair { split, .. } = self.paced_time();
         self.r.set_fg_colour(colour::Key::Pace(split.pace));
         self.r.put_str_r(&time_str(split.time))
     }
 
+    fn draw_skipped(&mut self) -> Result<()> {
+        self.r.set_font(self.font)?;
+        self.r.set_fg_colour(colour::Key::NoTime);
+        self.r.put_str_r(SKIPPED_TIME)
+    }
+
     fn draw_time_placeholder(&mut self) -> Result<()> {
-        self.r.set_font(font::Id::Normal)?;
+        self.r.set_font(self.font)?;
         self.r.set_fg_colour(colour::Key::NoTime);
-        self.r.put_str_r("--'--\"---")
+        self.r.put_str_r(PLACEHOLDER_TIME)
+    }
+
+    /// Gets the editor open on this split, if any.
+    fn editor(&self) -> Option<&'p crate::presenter::Editor> {
+        self.p.editor().filter(|e| e.cur.position() == self.index)
+    }
+
+    /// Gets the colour of the split's name: a flashing gold takes priority
+    /// over the cursor, which takes priority over search matches, which take
+    /// priority over the split's position.
+    fn name_colour(&self) -> colour::Key {
+        if flash::is_lit(self.p, Target::Gold(self.index)) {
+            return colour::Key::Pace(model::pace::Pace::PersonalBest);
+        }
+        let position = self.position();
+        if position != cursor::SplitPosition::Cursor && self.p.is_search_match(self.index) {
+            colour::Key::SearchMatch
+        } else {
+            colour::Key::Name(position)
+        }
     }
 
     fn position(&self) -> cursor::SplitPosition {
@@ -105,7 +377,20 @@ impl<'r, 'g, 'p, 's> SplitDrawer<'r, 'g, 'p, 's> {
     }
 }
 
+/// The placeholder shown in place of a time for splits without one.
+const PLACEHOLDER_TIME: &str = "--'--\"---";
+
+/// Shown in place of a time for skipped splits.
+const SKIPPED_TIME: &str = "skipped";
+
 #[must_use]
 pub fn time_str(time: model::time::Time) -> String {
     format!("{}'{}\"{}", time.mins, time.secs, time.millis)
 }
+
+/// Formats `delta` as a time, signed `-` if ahead and `+` if behind.
+#[must_use]
+pub fn delta_str(delta: model::pace::Delta) -> String {
+    let sign = if delta.ahead { '-' } else { '+' };
+    format!("{sign}{}", time_str(delta.time))
+}
@@ -0,0 +1,84 @@
+//! Scrolling logic for the split viewer.
+
+use std::ops::Range;
+
+/// A scrolling viewport onto a list of rows.
+///
+/// The viewport remembers its scroll offset between redraws, so that it only
+/// moves when it needs to.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Viewport {
+    /// The index of the first row in view.
+    offset: usize,
+}
+
+impl Viewport {
+    /// Scrolls the viewport so that, out of `len` rows, the row at `focus` is
+    /// in a view of `cap` rows, then returns the range of rows in view.
+    ///
+    /// Where possible, at least `previous` rows stay in view before the focus,
+    /// and `upcoming` rows after it.  If the view is too small for both, the
+    /// previous rows take priority.
+    pub fn scroll(
+        &mut self,
+        focus: usize,
+        len: usize,
+        cap: usize,
+        previous: usize,
+        upcoming: usize,
+    ) -> Range<usize> {
+        if len <= cap {
+            self.offset = 0;
+            return 0..len;
+        }
+        if cap == 0 {
+            return 0..0;
+        }
+
+        let previous = previous.min(cap - 1);
+        let upcoming = upcoming.min(cap - 1 - previous);
+        let focus = focus.min(len - 1);
+
+        let min_offset = (focus + upcoming + 1).saturating_sub(cap);
+        let max_offset = focus.saturating_sub(previous);
+        self.offset = self.offset.clamp(min_offset, max_offset).min(len - cap);
+        self.offset..(self.offset + cap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn scroll_fits() {
+        let mut v = super::Viewport::default();
+        assert_eq!(v.scroll(3, 5, 10, 1, 1), 0..5);
+    }
+
+    #[test]
+    fn scroll_down_keeps_upcoming() {
+        let mut v = super::Viewport::default();
+        assert_eq!(v.scroll(0, 20, 5, 1, 2), 0..5);
+        assert_eq!(v.scroll(2, 20, 5, 1, 2), 0..5);
+        assert_eq!(v.scroll(3, 20, 5, 1, 2), 1..6);
+    }
+
+    #[test]
+    fn scroll_up_keeps_previous() {
+        let mut v = super::Viewport::default();
+        assert_eq!(v.scroll(10, 20, 5, 1, 2), 8..13);
+        assert_eq!(v.scroll(9, 20, 5, 1, 2), 8..13);
+        assert_eq!(v.scroll(8, 20, 5, 1, 2), 7..12);
+    }
+
+    #[test]
+    fn scroll_clamps_to_end() {
+        let mut v = super::Viewport::default();
+        assert_eq!(v.scroll(19, 20, 5, 1, 2), 15..20);
+    }
+
+    #[test]
+    fn scroll_prefers_previous() {
+        let mut v = super::Viewport::default();
+        assert_eq!(v.scroll(10, 20, 2, 3, 3), 9..11);
+    }
+}
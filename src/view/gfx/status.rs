@@ -0,0 +1,71 @@
+//! The status line widget.
+
+use super::{
+    colour, font, metrics,
+    position::{Position, X},
+    render::{Region, Renderer},
+    widget,
+};
+use crate::{
+    model::pace::Pace,
+    presenter::{prompt::Prompt, Presenter},
+    view::{error::Result, keys},
+};
+
+/// Views any celebration message or prompt the presenter has for the user,
+/// and any count or prefix typed ahead of a command.
+pub struct Widget<'a> {
+    /// The bounding box for the status widget.
+    pub rect: metrics::Rect,
+    /// The font for the widget's text.
+    pub font: font::Id,
+    /// The keybindings, used to name the keys that answer prompts.
+    pub keys: &'a keys::Config,
+}
+
+impl widget::Widget for Widget<'_> {
+    fn render(&mut self, r: &mut dyn Renderer, p: &Presenter) -> Result<()> {
+        if let Some(flash) = p.flash() {
+            let mut r = Region::new(r, self.rect);
+            r.set_pos(Position::top_left(0, 0));
+            r.set_font(self.font)?;
+            r.set_fg_colour(colour::Key::Pace(Pace::PersonalBest));
+            r.put_str(&flash.message)?;
+        } else if let Some(prompt) = p.prompt() {
+            let mut r = Region::new(r, self.rect);
+            r.set_pos(Position::top_left(0, 0));
+            r.set_font(self.font)?;
+            r.set_fg_colour(colour::Key::Header);
+            r.put_str(&self.describe(&prompt))?;
+        }
+        let pending = p.pending();
+        if !pending.is_empty() {
+            let mut r = Region::new(r, self.rect);
+            r.set_pos(Position::x(X::Right(0)));
+            r.set_font(self.font)?;
+            r.set_fg_colour(colour::Key::Header);
+            r.put_str_r(&pending.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+impl Widget<'_> {
+    /// Describes `prompt`, naming the first key bound to each hinted event.
+    ///
+    /// Hints at events with no keys bound are left out.
+    fn describe(&self, prompt: &Prompt) -> String {
+        let hints: Vec<String> = prompt
+            .hints
+            .iter()
+            .filter_map(|h| {
+                let chord = self.keys.chord_for(&h.event)?;
+                Some(format!("{chord}: {}", h.label))
+            })
+            .collect();
+        if hints.is_empty() {
+            return prompt.text.to_owned();
+        }
+        format!("{} {}", prompt.text, hints.join(", "))
+    }
+}
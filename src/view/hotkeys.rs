@@ -0,0 +1,73 @@
+//! Global hotkeys, read straight from input devices.
+//!
+//! SDL only sees keys pressed while the zombiesplit window has focus, so
+//! timing a game in real time would otherwise mean switching windows to
+//! split.  Global hotkeys are instead read from Linux evdev devices in
+//! `/dev/input`, and so work whichever window has focus.  They need the
+//! `global-hotkeys` feature, and permission to read the devices (usually by
+//! being in the `input` group).
+
+use super::keys;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[cfg(all(target_os = "linux", feature = "global-hotkeys"))]
+mod device;
+#[cfg(all(target_os = "linux", feature = "global-hotkeys"))]
+pub use device::Listener;
+
+/// Global hotkey configuration.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Config {
+    /// Paths of the devices to read.
+    ///
+    /// If empty, every device that has any of the bound keys is read.
+    #[serde(default)]
+    pub devices: Vec<PathBuf>,
+    /// Bindings from chords to actions.
+    ///
+    /// Chords use evdev key names (for instance, `KEY_F1` or `ctrl+KEY_KP1`),
+    /// and there are no default bindings.
+    #[serde(default)]
+    pub keys: keys::Bindings,
+}
+
+/// Stand-in for the hotkey listener on builds without global hotkeys.
+#[cfg(not(all(target_os = "linux", feature = "global-hotkeys")))]
+pub struct Listener(std::convert::Infallible);
+
+#[cfg(not(all(target_os = "linux", feature = "global-hotkeys")))]
+impl Listener {
+    /// Fails to start listening, as global hotkeys aren't supported.
+    ///
+    /// # Errors
+    ///
+    /// Always returns [`Error::Unsupported`].
+    pub fn spawn(_: &Config) -> Result<Self> {
+        Err(Error::Unsupported)
+    }
+
+    /// Drains the actions sent by hotkeys since the last call.
+    pub fn actions(&self) -> impl Iterator<Item = keys::Action> + '_ {
+        std::iter::empty()
+    }
+}
+
+/// Enumeration of errors occurring when setting up global hotkeys.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("global hotkeys aren't supported by this build")]
+    Unsupported,
+    #[error("bad hotkey binding: {0}")]
+    Keys(#[from] keys::Error),
+    #[error("unknown key in hotkey binding '{0}'")]
+    UnknownKey(keys::Chord),
+    #[error("couldn't open input device: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("no input devices have any of the hotkeys")]
+    NoDevices,
+}
+
+/// Shorthand for results over [Error].
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,160 @@
+//! Listening for hotkeys on evdev devices.
+
+use super::{
+    super::keys::{self, Action, Chord, Modifiers},
+    Config, Error, Result,
+};
+use evdev::{Device, InputEventKind, Key};
+use std::{sync::mpsc, sync::Arc, thread};
+
+/// Listens for hotkeys on a set of devices.
+///
+/// Each device is read on its own thread, which sends the action of each
+/// bound chord as it is pressed.  Threads end when they next see an event
+/// after the listener is dropped, or when their device fails.
+pub struct Listener {
+    /// The channel on which device threads send actions.
+    rx: mpsc::Receiver<Action>,
+}
+
+impl Listener {
+    /// Starts listening for the hotkeys configured in `cfg`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bindings conflict or name unknown keys, or if
+    /// there are no devices to read.
+    pub fn spawn(cfg: &Config) -> Result<Self> {
+        let map = keys::Map::new(&cfg.keys)?;
+        let wanted = map.chords().map(key).collect::<Result<Vec<_>>>()?;
+        let devices = if cfg.devices.is_empty() {
+            evdev::enumerate()
+                .map(|(_, d)| d)
+                .filter(|d| {
+                    d.supported_keys()
+                        .is_some_and(|keys| wanted.iter().any(|k| keys.contains(*k)))
+                })
+                .collect()
+        } else {
+            cfg.devices
+                .iter()
+                .map(Device::open)
+                .collect::<std::io::Result<Vec<_>>>()?
+        };
+        if devices.is_empty() {
+            return Err(Error::NoDevices);
+        }
+
+        let (tx, rx) = mpsc::channel();
+        let map = Arc::new(map);
+        for device in devices {
+            let tx = tx.clone();
+            let map = Arc::clone(&map);
+            thread::spawn(move || listen(device, &map, &tx));
+        }
+        Ok(Self { rx })
+    }
+
+    /// Drains the actions sent by hotkeys since the last call.
+    pub fn actions(&self) -> impl Iterator<Item = Action> + '_ {
+        self.rx.try_iter()
+    }
+}
+
+/// Gets the evdev key named by `chord`.
+fn key(chord: &Chord) -> Result<Key> {
+    chord
+        .key_name()
+        .to_uppercase()
+        .parse()
+        .map_err(|_| Error::UnknownKey(chord.clone()))
+}
+
+/// Reads `device` until it fails or `tx` hangs up, sending the action for
+/// each chord in `map` pressed.
+fn listen(mut device: Device, map: &keys::Map, tx: &mpsc::Sender<Action>) {
+    let mut mods = Modifiers::default();
+    while let Ok(events) = device.fetch_events() {
+        for event in events {
+            let InputEventKind::Key(key) = event.kind() else {
+                continue;
+            };
+            // 0 is release, 1 is press, and 2 is autorepeat.
+            let held = event.value() != 0;
+            match key {
+                Key::KEY_LEFTSHIFT | Key::KEY_RIGHTSHIFT => mods.shift = held,
+                Key::KEY_LEFTCTRL | Key::KEY_RIGHTCTRL => mods.ctrl = held,
+                Key::KEY_LEFTALT | Key::KEY_RIGHTALT => mods.alt = held,
+                _ if event.value() == 1 => {
+                    let chord = Chord::new(&format!("{key:?}"), mods);
+                    if let Some(action) = map.action(&chord) {
+                        if tx.send(action).is_err() {
+                            return;
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use evdev::{uinput::VirtualDeviceBuilder, AttributeSet, EventType, InputEvent};
+    use std::time::{Duration, Instant};
+
+    /// Tests that hotkeys on a virtual device send the right actions,
+    /// honouring modifiers.
+    #[test]
+    #[ignore = "needs write access to /dev/uinput"]
+    fn virtual_device() {
+        let mut keys = AttributeSet::<Key>::new();
+        keys.insert(Key::KEY_F1);
+        keys.insert(Key::KEY_LEFTCTRL);
+        let mut dev = VirtualDeviceBuilder::new()
+            .unwrap()
+            .name("zombiesplit test")
+            .with_keys(&keys)
+            .unwrap()
+            .build()
+            .unwrap();
+        let node = dev.enumerate_dev_nodes_blocking().unwrap().next().unwrap();
+
+        let cfg: Config = toml::from_str(&format!(
+            r#"
+            devices = [{:?}]
+            [keys]
+            up = ["KEY_F1"]
+            down = ["ctrl+key_f1"]
+            "#,
+            node.unwrap()
+        ))
+        .unwrap();
+        let listener = Listener::spawn(&cfg).unwrap();
+
+        let press = |k: Key, v: i32| InputEvent::new(EventType::KEY, k.code(), v);
+        dev.emit(&[press(Key::KEY_F1, 1), press(Key::KEY_F1, 0)])
+            .unwrap();
+        dev.emit(&[press(Key::KEY_LEFTCTRL, 1), press(Key::KEY_F1, 1)])
+            .unwrap();
+        dev.emit(&[press(Key::KEY_F1, 0), press(Key::KEY_LEFTCTRL, 0)])
+            .unwrap();
+
+        let mut actions = vec![];
+        let deadline = Instant::now() + Duration::from_secs(1);
+        while actions.len() < 2 && Instant::now() < deadline {
+            actions.extend(listener.actions());
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(actions, vec![Action::Up, Action::Down]);
+    }
+
+    /// Tests that bindings to keys evdev doesn't know are rejected.
+    #[test]
+    fn unknown_key() {
+        let cfg: Config = toml::from_str("[keys]\nup = [\"KEY_NOPE\"]").unwrap();
+        assert!(matches!(Listener::spawn(&cfg), Err(Error::UnknownKey(_))));
+    }
+}
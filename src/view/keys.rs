@@ -0,0 +1,497 @@
+//! Remappable keybindings.
+//!
+//! Keybindings map chords (key names, plus modifiers) to [Action]s, which in
+//! turn map onto presenter events.  The `[keys]` section of the system config
+//! maps action names to lists of chords; any action it mentions has its
+//! default bindings replaced, and any action it leaves out keeps them.
+
+use crate::{
+    model::time::position,
+    presenter::{
+        cursor,
+        event::{Edit, Event},
+        practice, search,
+    },
+};
+use serde::{Deserialize, Serialize};
+use serde_with::{DeserializeFromStr, SerializeDisplay};
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::TryFrom,
+    fmt::{self, Display},
+    str::FromStr,
+};
+use thiserror::Error;
+
+/// Something that can be bound to a key.
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, DeserializeFromStr, SerializeDisplay,
+)]
+pub enum Action {
+    /// Add a digit to the current editor.
+    Digit(u8),
+    /// Remove the last digit from the current editor.
+    Remove,
+    /// Edit the minutes field.
+    EditMinutes,
+    /// Edit the seconds field.
+    EditSeconds,
+    /// Edit the milliseconds field.
+    EditMilliseconds,
+    /// Move the cursor up.
+    Up,
+    /// Move the cursor down.
+    Down,
+    /// Move to the first split.
+    First,
+    /// Move to the last split.
+    Last,
+    /// Move to the start of the previous group.
+    PrevGroup,
+    /// Move to the start of the next group.
+    NextGroup,
+    /// Go to a split (pressed twice, like vi's `gg`).
+    Goto,
+    /// Undo something.
+    Undo,
+    /// Commit something.
+    Commit,
+    /// Delete something.
+    Delete,
+    /// Skip the split under the cursor.
+    Skip,
+    /// Start a new run.
+    NewRun,
+    /// Pick another game or category.
+    Pick,
+    /// Practise the split under the cursor.
+    PracticeSplit,
+    /// Practise the group around the cursor.
+    PracticeGroup,
+    /// Open the command line.
+    Command,
+    /// Start searching for a split by name.
+    Search,
+    /// Move to the next match of the last search.
+    SearchNext,
+    /// Move to the previous match of the last search.
+    SearchPrev,
+    /// Quit zombiesplit.
+    Quit,
+}
+
+/// The names of every action other than [`Action::Digit`].
+const NAMES: &[(Action, &str)] = &[
+    (Action::Remove, "remove"),
+    (Action::EditMinutes, "edit-minutes"),
+    (Action::EditSeconds, "edit-seconds"),
+    (Action::EditMilliseconds, "edit-milliseconds"),
+    (Action::Up, "up"),
+    (Action::Down, "down"),
+    (Action::First, "first"),
+    (Action::Last, "last"),
+    (Action::PrevGroup, "prev-group"),
+    (Action::NextGroup, "next-group"),
+    (Action::Goto, "goto"),
+    (Action::Undo, "undo"),
+    (Action::Commit, "commit"),
+    (Action::Delete, "delete"),
+    (Action::Skip, "skip"),
+    (Action::NewRun, "new-run"),
+    (Action::Pick, "pick"),
+    (Action::PracticeSplit, "practice-split"),
+    (Action::PracticeGroup, "practice-group"),
+    (Action::Command, "command"),
+    (Action::Search, "search"),
+    (Action::SearchNext, "search-next"),
+    (Action::SearchPrev, "search-prev"),
+    (Action::Quit, "quit"),
+];
+
+/// The prefix of digit action names.
+const DIGIT_PREFIX: &str = "digit-";
+
+impl Action {
+    /// Gets the presenter event this action sends.
+    #[must_use]
+    pub fn event(self) -> Event {
+        match self {
+            Self::Digit(d) => Event::digit(d),
+            Self::Remove => Event::Edit(Edit::Remove),
+            // We don't allow entering hours yet, but this may change.
+            Self::EditMinutes => Event::EnterField(position::Name::Minutes),
+            Self::EditSeconds => Event::EnterField(position::Name::Seconds),
+            Self::EditMilliseconds => Event::EnterField(position::Name::Milliseconds),
+            Self::Up => Event::motion(cursor::Motion::Up),
+            Self::Down => Event::motion(cursor::Motion::Down),
+            Self::First => Event::motion(cursor::Motion::First),
+            Self::Last => Event::motion(cursor::Motion::Last),
+            Self::PrevGroup => Event::motion(cursor::Motion::PrevGroup),
+            Self::NextGroup => Event::motion(cursor::Motion::NextGroup),
+            Self::Goto => Event::Goto,
+            Self::Undo => Event::Undo,
+            Self::Commit => Event::Commit,
+            Self::Delete => Event::Delete,
+            Self::Skip => Event::Skip,
+            Self::NewRun => Event::NewRun,
+            Self::Pick => Event::Pick,
+            Self::PracticeSplit => Event::Practice(practice::Scope::Split),
+            Self::PracticeGroup => Event::Practice(practice::Scope::Group),
+            Self::Command => Event::Command,
+            Self::Search => Event::Search,
+            Self::SearchNext => Event::SearchAgain(search::Direction::Forward),
+            Self::SearchPrev => Event::SearchAgain(search::Direction::Backward),
+            Self::Quit => Event::Quit,
+        }
+    }
+}
+
+impl Display for Action {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Self::Digit(d) = self {
+            return write!(f, "{DIGIT_PREFIX}{d}");
+        }
+        let (_, name) = NAMES
+            .iter()
+            .find(|(a, _)| a == self)
+            .expect("every non-digit action should have a name");
+        f.write_str(name)
+    }
+}
+
+impl FromStr for Action {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if let Some(d) = s.strip_prefix(DIGIT_PREFIX) {
+            return match d.parse() {
+                Ok(d) if d < 10 => Ok(Self::Digit(d)),
+                _ => Err(Error::UnknownAction(s.to_owned())),
+            };
+        }
+        NAMES
+            .iter()
+            .find(|(_, name)| *name == s)
+            .map(|(a, _)| *a)
+            .ok_or_else(|| Error::UnknownAction(s.to_owned()))
+    }
+}
+
+/// A key, along with the modifiers that must be held with it.
+///
+/// Chords are written as the SDL name of the key, optionally preceded by
+/// modifiers (`shift+`, `ctrl+`, `alt+`): for instance, `j`, `Keypad 1`, or
+/// `shift+p`.  Key names are case-insensitive.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, DeserializeFromStr, SerializeDisplay)]
+pub struct Chord {
+    /// The name of the key, in lower case.
+    key: String,
+    /// The modifiers that must be held.
+    mods: Modifiers,
+}
+
+/// The modifiers held with a key.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    /// Whether shift is held.
+    pub shift: bool,
+    /// Whether control is held.
+    pub ctrl: bool,
+    /// Whether alt is held.
+    pub alt: bool,
+}
+
+impl Chord {
+    /// Constructs a chord for the key named `key`, with modifiers `mods`.
+    #[must_use]
+    pub fn new(key: &str, mods: Modifiers) -> Self {
+        Self {
+            key: key.to_lowercase(),
+            mods,
+        }
+    }
+
+    /// Constructs a chord for the key named `key`, with no modifiers.
+    #[must_use]
+    pub fn key(key: &str) -> Self {
+        Self::new(key, Modifiers::default())
+    }
+
+    /// Constructs a chord for an SDL keypress.
+    #[must_use]
+    pub fn from_sdl(key: sdl2::keyboard::Keycode, m: sdl2::keyboard::Mod) -> Self {
+        use sdl2::keyboard::Mod;
+        let mods = Modifiers {
+            shift: m.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD),
+            ctrl: m.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD),
+            alt: m.intersects(Mod::LALTMOD | Mod::RALTMOD),
+        };
+        Self::new(&key.name(), mods)
+    }
+
+    /// Gets the name of the key, in lower case.
+    #[must_use]
+    pub fn key_name(&self) -> &str {
+        &self.key
+    }
+
+    /// Gets the modifiers that must be held with the key.
+    #[must_use]
+    pub fn modifiers(&self) -> Modifiers {
+        self.mods
+    }
+}
+
+impl Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (held, name) in &[
+            (self.mods.shift, "shift"),
+            (self.mods.ctrl, "ctrl"),
+            (self.mods.alt, "alt"),
+        ] {
+            if *held {
+                write!(f, "{name}+")?;
+            }
+        }
+        f.write_str(&self.key)
+    }
+}
+
+impl FromStr for Chord {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut chord = Self::key(s.trim());
+        // Peel modifiers off the front; what remains is the key name, which
+        // might itself contain a '+' (for instance, `Keypad +`).
+        loop {
+            let flag = if let Some(rest) = chord.key.strip_prefix("shift+") {
+                chord.key = rest.to_owned();
+                &mut chord.mods.shift
+            } else if let Some(rest) = chord.key.strip_prefix("ctrl+") {
+                chord.key = rest.to_owned();
+                &mut chord.mods.ctrl
+            } else if let Some(rest) = chord.key.strip_prefix("alt+") {
+                chord.key = rest.to_owned();
+                &mut chord.mods.alt
+            } else {
+                break;
+            };
+            *flag = true;
+        }
+        if chord.key.is_empty() {
+            return Err(Error::EmptyKey(s.to_owned()));
+        }
+        Ok(chord)
+    }
+}
+
+/// Bindings as written in config: a list of chords for each action.
+pub type Bindings = BTreeMap<Action, Vec<Chord>>;
+
+/// Keybinding configuration.
+///
+/// This is checked for conflicts when it is loaded.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(try_from = "Bindings", into = "Bindings")]
+pub struct Config {
+    /// The bindings overridden in config.
+    overrides: Bindings,
+    /// The chords bound to each action, once overrides are applied.
+    bindings: Bindings,
+    /// The action bound to each chord, once overrides are applied.
+    map: Map,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::try_from(Bindings::new()).expect("default bindings should not conflict")
+    }
+}
+
+impl TryFrom<Bindings> for Config {
+    type Error = Error;
+
+    fn try_from(overrides: Bindings) -> Result<Self> {
+        let mut bindings = defaults();
+        bindings.extend(overrides.clone());
+        let map = Map::new(&bindings)?;
+        Ok(Self {
+            overrides,
+            bindings,
+            map,
+        })
+    }
+}
+
+impl From<Config> for Bindings {
+    fn from(c: Config) -> Self {
+        c.overrides
+    }
+}
+
+impl Config {
+    /// Gets the map of chords to actions, with defaults and overrides
+    /// applied.
+    #[must_use]
+    pub fn map(&self) -> &Map {
+        &self.map
+    }
+
+    /// Gets the first chord bound to an action sending `event`, if any.
+    #[must_use]
+    pub fn chord_for(&self, event: &Event) -> Option<&Chord> {
+        self.bindings
+            .iter()
+            .filter(|(action, _)| action.event() == *event)
+            .find_map(|(_, chords)| chords.first())
+    }
+}
+
+/// A map from chords to the actions bound to them.
+#[derive(Clone, Debug, Default)]
+pub struct Map(HashMap<Chord, Action>);
+
+impl Map {
+    /// Builds a map from `bindings`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `bindings` binds a chord to more than one action.
+    pub fn new(bindings: &Bindings) -> Result<Self> {
+        let mut map = HashMap::new();
+        for (action, chords) in bindings {
+            for chord in chords {
+                if let Some(first) = map.insert(chord.clone(), *action) {
+                    return Err(Error::Conflict {
+                        chord: chord.clone(),
+                        first,
+                        second: *action,
+                    });
+                }
+            }
+        }
+        Ok(Self(map))
+    }
+
+    /// Gets the action bound to `chord`, if any.
+    #[must_use]
+    pub fn action(&self, chord: &Chord) -> Option<Action> {
+        self.0.get(chord).copied()
+    }
+
+    /// Iterates over every bound chord.
+    pub fn chords(&self) -> impl Iterator<Item = &Chord> {
+        self.0.keys()
+    }
+}
+
+/// Gets the default bindings.
+fn defaults() -> Bindings {
+    let mut bindings: Bindings = (0..10)
+        .map(|d| (Action::Digit(d), chords(&[&d.to_string()])))
+        .collect();
+    bindings.extend(vec![
+        (Action::Remove, chords(&["Backspace"])),
+        (Action::EditMinutes, chords(&["m"])),
+        (Action::EditSeconds, chords(&["s"])),
+        (Action::EditMilliseconds, chords(&["."])),
+        (Action::Up, chords(&["k", "Up"])),
+        (Action::Down, chords(&["j", "Down", "Space"])),
+        (Action::First, chords(&["Home"])),
+        (Action::Last, chords(&["shift+g", "End"])),
+        (Action::PrevGroup, chords(&["shift+["])),
+        (Action::NextGroup, chords(&["shift+]"])),
+        (Action::Goto, chords(&["g"])),
+        (Action::Undo, chords(&["h", "Left"])),
+        (Action::Commit, chords(&["l", "Right"])),
+        (Action::Delete, chords(&["x", "Delete"])),
+        (Action::Skip, chords(&["shift+s"])),
+        (Action::NewRun, chords(&["Return", "z"])),
+        (Action::Pick, chords(&["c"])),
+        (Action::PracticeSplit, chords(&["p"])),
+        (Action::PracticeGroup, chords(&["shift+p"])),
+        (Action::Command, chords(&["shift+;"])),
+        (Action::Search, chords(&["/"])),
+        (Action::SearchNext, chords(&["n"])),
+        (Action::SearchPrev, chords(&["shift+n"])),
+        (Action::Quit, chords(&["Escape"])),
+    ]);
+    bindings
+}
+
+/// Parses each of `names` as a chord.
+fn chords(names: &[&str]) -> Vec<Chord> {
+    names
+        .iter()
+        .map(|n| n.parse().expect("default chords should parse"))
+        .collect()
+}
+
+/// Enumeration of errors occurring when interpreting keybindings.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("unknown action '{0}'")]
+    UnknownAction(String),
+    #[error("no key given in '{0}'")]
+    EmptyKey(String),
+    #[error("buttons can't have modifiers, as in '{0}'")]
+    ModifiedButton(Chord),
+    #[error("'{chord}' is bound to both '{first}' and '{second}'")]
+    Conflict {
+        chord: Chord,
+        first: Action,
+        second: Action,
+    },
+}
+
+/// Shorthand for results over [Error].
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tests that chords parse modifiers, and keep `+` in key names.
+    #[test]
+    fn chord_parse() {
+        let chord: Chord = "ctrl+Shift+Keypad +".parse().unwrap();
+        assert_eq!(chord.key_name(), "keypad +");
+        assert_eq!(chord.to_string(), "shift+ctrl+keypad +");
+        assert!(matches!("shift+".parse::<Chord>(), Err(Error::EmptyKey(_))));
+    }
+
+    /// Tests that overriding an action replaces its defaults, and that
+    /// conflicts with other actions are caught.
+    #[test]
+    fn config_overrides() {
+        let cfg: Config = toml::from_str(
+            r#"
+            down = ["Keypad 2", "j"]
+            digit-2 = []
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            cfg.map().action(&Chord::key("keypad 2")),
+            Some(Action::Down)
+        );
+        assert_eq!(cfg.map().action(&Chord::key("Space")), None);
+        assert_eq!(cfg.map().action(&Chord::key("2")), None);
+        assert_eq!(cfg.map().action(&Chord::key("k")), Some(Action::Up));
+
+        assert_eq!(
+            cfg.chord_for(&Event::motion(cursor::Motion::Down)),
+            Some(&Chord::key("keypad 2"))
+        );
+        assert_eq!(cfg.chord_for(&Event::digit(2)), None);
+
+        let err = toml::from_str::<Config>(r#"quit = ["j"]"#).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("'j' is bound to both 'down' and 'quit'"),
+            "unexpected error: {}",
+            err
+        );
+    }
+}